@@ -0,0 +1,580 @@
+//! Requests to the Advent of Code site, to download the inputs and submit the answers
+
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use regex::Regex;
+
+use crate::{
+    inputs::{get_input_from_input_dir, read_file, DayInput},
+    runner::{parse_day, run_part, selected_parts, PartSpec},
+    ResultRepr,
+};
+
+/// Session cookie for the Advent of Code site, from `AOC_SESSION` or the session file
+fn session_token(session_file: &Path) -> Result<String, Box<dyn Error>> {
+    let token = match env::var("AOC_SESSION") {
+        Ok(token) => token,
+        Err(_) if session_file.exists() => read_file(session_file)?,
+        Err(_) => {
+            return Err(format!(
+                "No session cookie: set AOC_SESSION or write it in {}",
+                session_file.to_string_lossy()
+            )
+            .into())
+        }
+    };
+    Ok(token.trim().to_owned())
+}
+
+/// Quote a value for the curl config, escaping what would end it early
+fn curl_quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Make a request to the Advent of Code site, giving the body of the response
+///
+/// The request is made with `curl`, passing the session cookie on its stdin so that
+/// it does not show in the process list
+///
+/// Giving a form posts it, as `(name, value)` pairs
+fn aoc_request(url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, Box<dyn Error>> {
+    let mut curl = process::Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--config", "-"])
+        .args([
+            "--user-agent",
+            concat!("year2022/", env!("CARGO_PKG_VERSION")),
+        ])
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Error while starting curl: {err}"))?;
+    let mut config = format!(
+        "header = {}\n",
+        curl_quote(&format!("Cookie: session={session}"))
+    );
+    for (name, value) in form {
+        config.push_str(&format!(
+            "data-urlencode = {}\n",
+            curl_quote(&format!("{name}={value}"))
+        ))
+    }
+    curl.stdin.take().unwrap().write_all(config.as_bytes())?;
+    let output = curl.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "Request to {url} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Response of the Advent of Code site to an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was given too recently
+    Wait,
+    /// The response was not understood
+    Unknown,
+}
+impl Verdict {
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wait => "wait",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        [
+            Verdict::Right,
+            Verdict::Wrong,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wait,
+            Verdict::Unknown,
+        ]
+        .into_iter()
+        .find(|v| v.name() == name)
+    }
+
+    /// Read the verdict in the page answering a submission
+    fn from_response(page: &str) -> Self {
+        if page.contains("That's the right answer") {
+            Verdict::Right
+        } else if page.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if page.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if page.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if page.contains("You gave an answer too recently") {
+            Verdict::Wait
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// The answer is known to be wrong
+    fn rejected(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+/// A past submission, as (day, part, verdict, answer)
+type Submission = (u8, usize, Verdict, String);
+
+/// Read the submission log, where each line is `timestamp day part verdict answer`
+fn load_submissions(path: &Path) -> Result<Vec<Submission>, Box<dyn Error>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let mut submissions = vec![];
+    for line in read_file(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("Invalid submission line {line:?}");
+        let mut fields = line.splitn(5, ' ');
+        let (Some(_), Some(day), Some(part), Some(verdict), Some(answer)) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(invalid().into());
+        };
+        submissions.push((
+            day.parse().map_err(|_| invalid())?,
+            part.parse().map_err(|_| invalid())?,
+            Verdict::from_name(verdict).ok_or_else(invalid)?,
+            answer.to_owned(),
+        ))
+    }
+    Ok(submissions)
+}
+
+/// Why an answer should not be submitted, given the previous submissions of its part
+fn already_judged(answer: &str, previous: &[&Submission]) -> Option<String> {
+    for (_, _, verdict, submitted) in previous {
+        match verdict {
+            Verdict::Right => return Some(format!("the part was already solved with {submitted}")),
+            verdict if verdict.rejected() && submitted == answer => {
+                return Some(format!("it was already rejected ({})", verdict.name()))
+            }
+            _ => (),
+        }
+    }
+    // numeric answers can be excluded by the hints of the previous ones
+    let value: i64 = answer.parse().ok()?;
+    previous.iter().find_map(|(_, _, verdict, submitted)| {
+        let submitted: i64 = submitted.parse().ok()?;
+        match verdict {
+            Verdict::TooHigh if value >= submitted => {
+                Some(format!("{submitted} was already too high"))
+            }
+            Verdict::TooLow if value <= submitted => {
+                Some(format!("{submitted} was already too low"))
+            }
+            _ => None,
+        }
+    })
+}
+
+/// The Advent of Code site, and where to find the session cookie for it
+pub struct Site {
+    /// Address of the site
+    pub base_url: String,
+    /// File with the session cookie, if `AOC_SESSION` is not set
+    pub session_file: PathBuf,
+}
+impl Site {
+    /// Address of a page of a day
+    fn day_url(&self, day: u8, page: &str) -> String {
+        format!(
+            "{}/2022/day/{day}{page}",
+            self.base_url.trim_end_matches('/')
+        )
+    }
+
+    /// Download the inputs of some days that are missing, in `dir`, waiting `delay` between downloads
+    pub fn fetch_inputs(
+        &self,
+        dir: &Path,
+        days: &[u8],
+        delay: Duration,
+    ) -> Result<(), Box<dyn Error>> {
+        let mut session = None;
+        let mut last_request: Option<Instant> = None;
+        let mut failed = 0;
+        for &d in days {
+            let path = get_input_from_input_dir(d, dir, "input");
+            if path.exists() {
+                eprintln!("Day {d}: already fetched");
+                continue;
+            }
+            let session = match &session {
+                Some(session) => session,
+                None => session.insert(session_token(&self.session_file)?),
+            };
+            // the site asks not to be hammered
+            if let Some(last) = last_request {
+                thread::sleep(delay.saturating_sub(last.elapsed()))
+            }
+            last_request = Some(Instant::now());
+            let url = self.day_url(d, "/input");
+            let fetched = aoc_request(&url, session, &[]).and_then(|input| {
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(&path, input)?;
+                Ok(())
+            });
+            match fetched {
+                Ok(()) => eprintln!("Day {d}: written to {}", path.to_string_lossy()),
+                Err(err) => {
+                    eprintln!("Day {d}: {err}");
+                    failed += 1
+                }
+            }
+        }
+        if failed > 0 {
+            return Err(format!("{failed} inputs could not be fetched").into());
+        }
+        Ok(())
+    }
+
+    /// Solve a part on the real input in `dir` and submit its answer, recording it in `log`
+    pub fn submit_answer(
+        &self,
+        dir: &Path,
+        d: u8,
+        part: usize,
+        overrides: &[(String, String)],
+        log: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        let day = crate::day(d).ok_or_else(|| format!("Day {d} is unsolved"))?;
+        selected_parts(day, PartSpec::Only(part))?;
+        let path = get_input_from_input_dir(d, dir, "input");
+        let (input, params) = DayInput::load(day, vec![part], Some(path), overrides).loaded?;
+        let (parsed, _) = parse_day(day, &input, &params)?;
+        let answer = match run_part(parsed.as_ref(), part, &input)?.res.repr() {
+            ResultRepr::Short(answer) => answer,
+            ResultRepr::Multiline(art) => {
+                return Err(format!("The answer must be read from the drawing:\n{art}").into())
+            }
+        };
+
+        let submissions = load_submissions(log)?;
+        let previous: Vec<_> = submissions
+            .iter()
+            .filter(|(sd, sp, _, _)| *sd == d && *sp == part)
+            .collect();
+        if let Some(reason) = already_judged(&answer, &previous) {
+            return Err(
+                format!("Not submitting {answer} for day {d} part {part}: {reason}").into(),
+            );
+        }
+
+        eprintln!("Submitting {answer} for day {d} part {part}...");
+        let session = session_token(&self.session_file)?;
+        let url = self.day_url(d, "/answer");
+        let page = aoc_request(
+            &url,
+            &session,
+            &[("level", &part.to_string()), ("answer", &answer)],
+        )?;
+        let verdict = Verdict::from_response(&page);
+
+        let timestamp = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)?
+            .as_secs();
+        let mut log_file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(log)
+            .map_err(|err| format!("Error while opening file {}: {err}", log.to_string_lossy()))?;
+        writeln!(
+            log_file,
+            "{timestamp} {d} {part} {} {answer}",
+            verdict.name()
+        )?;
+
+        // the message is in the article of the page
+        let message = page
+            .split_once("<article>")
+            .and_then(|(_, rest)| rest.split_once("</article>"))
+            .map_or(page.as_str(), |(article, _)| article);
+        let message = Regex::new("<[^>]*>").unwrap().replace_all(message, "");
+        println!("{}", message.trim());
+        match verdict {
+            Verdict::Right => Ok(()),
+            verdict => Err(format!("Answer not accepted ({})", verdict.name()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader, Read},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    /// A request received by the stub site, as (arrival time, request line, body)
+    type Request = (Instant, String, String);
+
+    /// Serve a stub of the site on a local port, answering each request with the page given by
+    /// `respond` for its path
+    ///
+    /// Gives the site, a fresh dir holding its session file, and the requests received
+    fn stub_site(
+        name: &str,
+        respond: fn(&str) -> String,
+    ) -> (Site, PathBuf, Arc<Mutex<Vec<Request>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let received = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    match header.trim().split_once(':') {
+                        Some((name, value)) if name.eq_ignore_ascii_case("content-length") => {
+                            length = value.trim().parse().unwrap()
+                        }
+                        Some(_) => (),
+                        None => break,
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request_line = request_line.trim().to_owned();
+                let page = respond(request_line.split(' ').nth(1).unwrap());
+                // recorded before answering, so that it is seen when the request returns
+                received.lock().unwrap().push((
+                    Instant::now(),
+                    request_line,
+                    String::from_utf8(body).unwrap(),
+                ));
+                write!(
+                    &stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}",
+                    page.len()
+                )
+                .unwrap();
+            }
+        });
+        let dir = env::temp_dir().join(format!("year2022-{name}-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let session_file = dir.join("session");
+        fs::write(&session_file, "stub-session\n").unwrap();
+        let site = Site {
+            base_url,
+            session_file,
+        };
+        (site, dir, requests)
+    }
+
+    #[test]
+    fn verdict_from_response() {
+        let page = |message: &str| format!("<main><article><p>{message}</p></article></main>");
+        let cases = [
+            (
+                "That's the right answer! You are one gold star closer.",
+                Verdict::Right,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                Verdict::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                Verdict::TooLow,
+            ),
+            (
+                "That's not the right answer. If you're stuck...",
+                Verdict::Wrong,
+            ),
+            (
+                "You gave an answer too recently; you have to wait.",
+                Verdict::Wait,
+            ),
+            (
+                "You don't seem to be solving the right level.",
+                Verdict::Unknown,
+            ),
+        ];
+        for (message, verdict) in cases {
+            assert_eq!(Verdict::from_response(&page(message)), verdict, "{message}")
+        }
+    }
+
+    #[test]
+    fn judged_answers() {
+        let submissions: Vec<Submission> = vec![
+            (1, 1, Verdict::TooHigh, "500".to_owned()),
+            (1, 1, Verdict::TooLow, "100".to_owned()),
+            (1, 1, Verdict::Wrong, "250".to_owned()),
+            (1, 1, Verdict::Wait, "300".to_owned()),
+        ];
+        let previous: Vec<_> = submissions.iter().collect();
+        // the hints bound the numeric answers
+        assert!(already_judged("500", &previous).is_some());
+        assert!(already_judged("600", &previous).is_some());
+        assert!(already_judged("100", &previous).is_some());
+        assert!(already_judged("50", &previous).is_some());
+        assert!(already_judged("250", &previous).is_some());
+        // an answer given while waiting was not judged
+        assert_eq!(already_judged("300", &previous), None);
+        assert_eq!(already_judged("101", &previous), None);
+        assert_eq!(already_judged("499", &previous), None);
+
+        let submissions: Vec<Submission> = vec![
+            (10, 2, Verdict::Wrong, "ABCDEFGH".to_owned()),
+            (10, 2, Verdict::Right, "EHPZPJGL".to_owned()),
+        ];
+        let previous: Vec<_> = submissions.iter().collect();
+        assert!(already_judged("ABCDEFGH", &previous).is_some());
+        // a solved part takes no more answers
+        assert!(already_judged("EHPZPJGL", &previous).is_some());
+        assert!(already_judged("ZZZZZZZZ", &previous).is_some());
+        assert_eq!(already_judged("ZZZZZZZZ", &previous[..1]), None);
+    }
+
+    #[test]
+    fn submissions_log() {
+        let path = env::temp_dir().join(format!("year2022-submissions-{}", process::id()));
+        fs::write(
+            &path,
+            "# timestamp day part verdict answer\n\
+             1670000000 1 1 too_high 500\n\
+             \n\
+             1670000100 1 2 right some answer\n",
+        )
+        .unwrap();
+        let submissions = load_submissions(&path);
+        fs::write(&path, "1670000000 1 1 maybe 500\n").unwrap();
+        let invalid = load_submissions(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            submissions.unwrap(),
+            vec![
+                (1, 1, Verdict::TooHigh, "500".to_owned()),
+                (1, 2, Verdict::Right, "some answer".to_owned()),
+            ]
+        );
+        assert!(invalid.is_err());
+        assert_eq!(load_submissions(&path).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn fetch_inputs() {
+        let (site, dir, requests) = stub_site("fetch", |path| format!("input at {path}\n"));
+        let inputs = dir.join("inputs");
+        fs::create_dir_all(inputs.join("2")).unwrap();
+        fs::write(inputs.join("2").join("input"), "already fetched\n").unwrap();
+        let delay = Duration::from_millis(400);
+        let fetched = site.fetch_inputs(&inputs, &[1, 2, 3], delay);
+        let contents: Vec<_> = [1, 2, 3]
+            .into_iter()
+            .map(|d| fs::read_to_string(inputs.join(d.to_string()).join("input")).ok())
+            .collect();
+        let requests = requests.lock().unwrap().clone();
+        fs::remove_dir_all(&dir).unwrap();
+
+        fetched.unwrap();
+        assert_eq!(
+            contents,
+            [
+                Some("input at /2022/day/1/input\n".to_owned()),
+                Some("already fetched\n".to_owned()),
+                Some("input at /2022/day/3/input\n".to_owned()),
+            ]
+        );
+        // the input already there is not downloaded again
+        let lines: Vec<_> = requests.iter().map(|(_, line, _)| line.as_str()).collect();
+        assert_eq!(
+            lines,
+            [
+                "GET /2022/day/1/input HTTP/1.1",
+                "GET /2022/day/3/input HTTP/1.1"
+            ]
+        );
+        // the arrival times also count the start of curl, that can be slower for the first one
+        assert!(requests[1].0 - requests[0].0 >= delay * 3 / 4);
+    }
+
+    #[test]
+    fn submit_answer() {
+        let (site, dir, requests) = stub_site("submit", |_| {
+            "<main><article><p>That's the right answer!</p></article></main>".to_owned()
+        });
+        let inputs = dir.join("inputs");
+        fs::create_dir_all(inputs.join("2")).unwrap();
+        fs::write(inputs.join("2").join("input"), "A Y\nB X\nC Z\n").unwrap();
+        let log = dir.join("submissions");
+        fs::write(&log, "1670000000 2 1 wrong 14\n1670000100 2 2 too_low 12\n").unwrap();
+        let submitted = site.submit_answer(&inputs, 2, 1, &[], &log);
+        // the answer of the second part, 12, was already rejected
+        let rejected = site.submit_answer(&inputs, 2, 2, &[], &log);
+        let logged = fs::read_to_string(&log).unwrap();
+        let requests = requests.lock().unwrap().clone();
+        fs::remove_dir_all(&dir).unwrap();
+
+        submitted.unwrap();
+        assert!(rejected.is_err());
+        let sent: Vec<_> = requests
+            .iter()
+            .map(|(_, line, body)| (line.as_str(), body.as_str()))
+            .collect();
+        assert_eq!(
+            sent,
+            [("POST /2022/day/2/answer HTTP/1.1", "level=1&answer=15")]
+        );
+        let lines: Vec<_> = logged.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[2].ends_with(" 2 1 right 15"), "{}", lines[2]);
+    }
+}
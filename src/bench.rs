@@ -0,0 +1,165 @@
+//! Benchmarks of the parts, and baselines to compare them with
+
+use std::{
+    error::Error,
+    fs,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    inputs::{read_file, DayInput},
+    runner::{parse_day, run_part},
+};
+
+/// Statistics over the repeated runs of a part
+#[derive(Debug, Clone, Copy)]
+pub struct BenchStats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+impl BenchStats {
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty());
+        samples.sort();
+        let runs = samples.len();
+        let median = if runs % 2 == 1 {
+            samples[runs / 2]
+        } else {
+            (samples[runs / 2 - 1] + samples[runs / 2]) / 2
+        };
+        let mean = samples.iter().map(|d| d.as_secs_f64()).sum::<f64>() / runs as f64;
+        let variance = if runs > 1 {
+            samples
+                .iter()
+                .map(|d| (d.as_secs_f64() - mean).powi(2))
+                .sum::<f64>()
+                / (runs - 1) as f64
+        } else {
+            0.
+        };
+        Self {
+            runs,
+            min: samples[0],
+            median,
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+}
+
+/// Benchmark of a part, as (day, part, stats)
+///
+/// Parsing is recorded as part 0
+pub type BenchRecord = (usize, usize, BenchStats);
+
+/// Time a step repeatedly, after a warm up run
+///
+/// It stops after `runs` runs or when `budget` is exausted, whichever comes first
+fn bench_step(
+    runs: Option<usize>,
+    budget: Option<Duration>,
+    mut step: impl FnMut() -> Result<Duration, Box<dyn Error>>,
+) -> Result<BenchStats, Box<dyn Error>> {
+    // warm up
+    step()?;
+
+    let start = Instant::now();
+    let mut samples = vec![];
+    while samples.is_empty()
+        || (runs.map_or(true, |runs| samples.len() < runs)
+            && budget.map_or(true, |budget| start.elapsed() < budget))
+    {
+        samples.push(step()?)
+    }
+    Ok(BenchStats::from_samples(samples))
+}
+
+/// Benchmark the parsing and all the requested parts
+pub fn bench_days(
+    to_run: &[Option<DayInput>],
+    runs: Option<usize>,
+    budget: Option<Duration>,
+) -> Result<Vec<BenchRecord>, Box<dyn Error>> {
+    let mut stats = vec![];
+    for (d, run) in to_run.iter().enumerate() {
+        if let Some(run) = run {
+            let day =
+                crate::day(d as u8 + 1).ok_or_else(|| format!("Day {} is unsolved", d + 1))?;
+            let (input, params) = run.loaded.as_ref().map_err(|err| err.clone())?;
+            eprintln!("Benchmarking day {} parsing...", d + 1);
+            stats.push((
+                d + 1,
+                0,
+                bench_step(runs, budget, || Ok(parse_day(day, input, params)?.1))?,
+            ));
+            let (parsed, _) = parse_day(day, input, params)?;
+            for &part in run.parts.iter() {
+                eprintln!("Benchmarking day {} part {part}...", d + 1);
+                stats.push((
+                    d + 1,
+                    part,
+                    bench_step(runs, budget, || {
+                        Ok(run_part(parsed.as_ref(), part, input)?.time)
+                    })?,
+                ))
+            }
+        }
+    }
+    Ok(stats)
+}
+
+/// Save the benchmarks as a baseline
+///
+/// Each line contains `day part runs min median mean stddev`, with the times in nanoseconds
+pub fn save_baseline(path: &Path, stats: &[BenchRecord]) -> Result<(), Box<dyn Error>> {
+    let mut content = String::from("# day part runs min median mean stddev\n");
+    for (day, part, s) in stats {
+        content.push_str(&format!(
+            "{day} {part} {} {} {} {} {}\n",
+            s.runs,
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.mean.as_nanos(),
+            s.stddev.as_nanos()
+        ))
+    }
+    fs::write(path, content).map_err(|err| {
+        format!(
+            "Error while writing file {}: {}",
+            path.to_string_lossy(),
+            err
+        )
+        .into()
+    })
+}
+
+pub fn load_baseline(path: &Path) -> Result<Vec<BenchRecord>, Box<dyn Error>> {
+    let mut stats = vec![];
+    for line in read_file(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<u64> = Result::from_iter(line.split_whitespace().map(|f| f.parse()))
+            .map_err(|err| format!("Invalid baseline line {line:?}: {err}"))?;
+        let [day, part, runs, min, median, mean, stddev] = fields[..] else {
+            return Err(format!("Invalid baseline line {line:?}: expected 7 fields").into());
+        };
+        stats.push((
+            day as usize,
+            part as usize,
+            BenchStats {
+                runs: runs as usize,
+                min: Duration::from_nanos(min),
+                median: Duration::from_nanos(median),
+                mean: Duration::from_nanos(mean),
+                stddev: Duration::from_nanos(stddev),
+            },
+        ))
+    }
+    Ok(stats)
+}
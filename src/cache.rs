@@ -0,0 +1,88 @@
+//! Cache of the results of the parts, kept on disk between the runs
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    env,
+    error::Error,
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    runner::{parse_worker_output, worker_output, RunResult},
+    Params,
+};
+
+/// On-disk cache of the results of the parts
+///
+/// Results are keyed by day, part, input, parameters and build, so a change to any of them
+/// solves the part again
+pub struct Cache {
+    dir: PathBuf,
+    /// Hash of the version and of the executable, changing with every build
+    build_id: u64,
+}
+impl Cache {
+    /// Open the cache in `dir`, if it exists or `create` is set
+    pub fn open(dir: &Path, create: bool) -> Result<Option<Self>, Box<dyn Error>> {
+        if create {
+            fs::create_dir_all(dir).map_err(|err| {
+                format!("Error while creating dir {}: {err}", dir.to_string_lossy())
+            })?;
+        } else if !dir.is_dir() {
+            return Ok(None);
+        }
+        let exe = fs::metadata(env::current_exe()?)?;
+        let mut hasher = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut hasher);
+        exe.len().hash(&mut hasher);
+        exe.modified()?.hash(&mut hasher);
+        Ok(Some(Self {
+            dir: dir.to_owned(),
+            build_id: hasher.finish(),
+        }))
+    }
+
+    /// File holding the result of a part
+    fn entry(&self, day: usize, part: usize, input: &str, params: &Params) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        self.build_id.hash(&mut hasher);
+        input.hash(&mut hasher);
+        let mut params: Vec<_> = params.iter().collect();
+        params.sort();
+        params.hash(&mut hasher);
+        self.dir
+            .join(format!("day{day}_part{part}_{:016x}", hasher.finish()))
+    }
+
+    pub fn get(&self, day: usize, part: usize, input: &str, params: &Params) -> Option<RunResult> {
+        let content = fs::read_to_string(self.entry(day, part, input, params)).ok()?;
+        match parse_worker_output(&content)? {
+            (_, Ok(res)) => Some(RunResult {
+                cached: true,
+                ..res
+            }),
+            (_, Err(_)) => None,
+        }
+    }
+
+    pub fn put(
+        &self,
+        day: usize,
+        part: usize,
+        input: &str,
+        params: &Params,
+        res: &RunResult,
+    ) -> Result<(), Box<dyn Error>> {
+        let path = self.entry(day, part, input, params);
+        fs::write(&path, worker_output(None, &Ok(res.clone()))).map_err(|err| {
+            format!(
+                "Error while writing file {}: {}",
+                path.to_string_lossy(),
+                err
+            )
+            .into()
+        })
+    }
+}
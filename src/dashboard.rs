@@ -0,0 +1,163 @@
+//! Live dashboard of a run, shown on the terminal while the parts are solved
+
+use std::{
+    io::{self, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use termion::{clear, color, cursor, screen::AlternateScreen};
+
+use crate::{
+    inputs::DayInput,
+    report::{day_title, format_duration, report_answer, report_time},
+    runner::{PartFailure, PartOutcome},
+};
+
+/// State of a part on the dashboard
+#[derive(Debug, Clone)]
+pub enum PartStatus {
+    Pending,
+    /// Its input is being parsed, since the given instant
+    Parsing(Instant),
+    /// It is being solved, since the given instant
+    Running(Instant),
+    Done(PartOutcome),
+}
+
+/// Live view of a run, redrawn on the terminal while the parts are solved
+pub struct Dashboard {
+    start: Instant,
+    /// Status of the requested parts of each day, as (part, status)
+    days: Mutex<Vec<Vec<(usize, PartStatus)>>>,
+}
+impl Dashboard {
+    pub fn new(to_run: &[Option<DayInput>]) -> Self {
+        let days = to_run
+            .iter()
+            .map(|run| match run {
+                Some(run) => {
+                    // inputs that failed to load are not run
+                    let status = match &run.loaded {
+                        Ok(_) => PartStatus::Pending,
+                        Err(err) => PartStatus::Done(Err(PartFailure::Error(err.clone()))),
+                    };
+                    run.parts
+                        .iter()
+                        .map(|&part| (part, status.clone()))
+                        .collect()
+                }
+                None => vec![],
+            })
+            .collect();
+        Self {
+            start: Instant::now(),
+            days: Mutex::new(days),
+        }
+    }
+
+    /// Change the status of a part of a day, or of all its parts
+    pub fn set(&self, d: usize, part: Option<usize>, status: PartStatus) {
+        let mut days = self.days.lock().unwrap();
+        for (p, s) in days[d].iter_mut() {
+            if part.map_or(true, |part| part == *p) {
+                *s = status.clone()
+            }
+        }
+    }
+
+    /// Redraw the dashboard until `done` is set
+    pub fn show(&self, done: &AtomicBool) -> io::Result<()> {
+        let mut screen = AlternateScreen::from(io::stdout());
+        write!(screen, "{}{}", cursor::Hide, clear::All)?;
+        while !done.load(Ordering::Relaxed) {
+            write!(screen, "{}", self.draw())?;
+            screen.flush()?;
+            thread::sleep(Duration::from_millis(100));
+        }
+        write!(screen, "{}", cursor::Show)?;
+        screen.flush()
+    }
+
+    /// Draw the status of all days, with a summary line
+    fn draw(&self) -> String {
+        let days = self.days.lock().unwrap();
+        let (mut solved, mut total) = (0, Duration::ZERO);
+        let mut screen = String::new();
+        for (d, parts) in days.iter().enumerate() {
+            let day_status = if parts.is_empty() {
+                (color::Fg(color::LightBlack).to_string(), "-")
+            } else if parts
+                .iter()
+                .any(|(_, s)| matches!(s, PartStatus::Done(Err(_))))
+            {
+                (color::Fg(color::Red).to_string(), "failed")
+            } else if parts.iter().all(|(_, s)| matches!(s, PartStatus::Done(_))) {
+                (color::Fg(color::Green).to_string(), "done")
+            } else if parts.iter().all(|(_, s)| matches!(s, PartStatus::Pending)) {
+                (color::Fg(color::LightBlack).to_string(), "pending")
+            } else {
+                (color::Fg(color::Yellow).to_string(), "running")
+            };
+            screen.push_str(&format!(
+                "{}{}{:>2}  {:<26} {}{:<8}{}",
+                cursor::Goto(1, d as u16 + 3),
+                clear::CurrentLine,
+                d + 1,
+                day_title(d + 1),
+                day_status.0,
+                day_status.1,
+                color::Fg(color::Reset)
+            ));
+            for (part, status) in parts.iter() {
+                let (color, cell) = match status {
+                    PartStatus::Pending => (
+                        color::Fg(color::LightBlack).to_string(),
+                        "pending".to_owned(),
+                    ),
+                    PartStatus::Parsing(since) => (
+                        color::Fg(color::Yellow).to_string(),
+                        format!("parsing {}", format_duration(since.elapsed())),
+                    ),
+                    PartStatus::Running(since) => (
+                        color::Fg(color::Yellow).to_string(),
+                        format!("running {}", format_duration(since.elapsed())),
+                    ),
+                    PartStatus::Done(outcome) => {
+                        if let Ok(res) = outcome {
+                            solved += 1;
+                            total += res.time;
+                        }
+                        let (answer, multiline) = report_answer(outcome);
+                        let answer = match multiline {
+                            Some(_) => "<multiline>".to_owned(),
+                            None => answer,
+                        };
+                        let color = match outcome {
+                            Ok(_) => color::Fg(color::Green).to_string(),
+                            Err(_) => color::Fg(color::Red).to_string(),
+                        };
+                        (color, format!("{answer} ({})", report_time(outcome)))
+                    }
+                };
+                let cell: String = cell.chars().take(40).collect();
+                screen.push_str(&format!(
+                    "  {part}: {color}{cell:<40}{}",
+                    color::Fg(color::Reset)
+                ));
+            }
+        }
+        let requested: usize = days.iter().map(|parts| parts.len()).sum();
+        format!(
+            "{}{}Advent of Code 2022 - {solved}/{requested} parts solved, total {}, elapsed {}{screen}",
+            cursor::Goto(1, 1),
+            clear::CurrentLine,
+            format_duration(total),
+            format_duration(self.start.elapsed())
+        )
+    }
+}
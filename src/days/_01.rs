@@ -1,70 +1,54 @@
-use std::{error::Error, fmt::Display, num::ParseIntError};
+use std::error::Error;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
-fn elves_backpacks(input: &str) -> Result<Vec<Vec<i64>>, ParseIntError> {
+fn elves_backpacks(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
     Result::from_iter(input.split("\n\n").map(|pack| {
         Result::from_iter(
             pack.split("\n")
-                .filter(|line| line.trim() != "")
-                .map(|line| line.parse()),
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(|line| parse_at(line, "a number of calories")),
         )
     }))
 }
 
-#[derive(Debug)]
-enum InputError {
-    Empty,
-    LessThanThree,
+fn part1(backpacks: &[Vec<i64>]) -> Result<PuzzleResult, Box<dyn Error>> {
+    backpacks
+        .iter()
+        .map(|pack| pack.iter().sum())
+        .max()
+        .ok_or("Empty input".into())
+        .map(|v: i64| PuzzleResult::Numeric(v))
 }
 
-impl Error for InputError {}
-impl Display for InputError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                InputError::Empty => "Empty input",
-                InputError::LessThanThree => "Less than three backpacks",
-            }
-        )
+fn part2(backpacks: &[Vec<i64>]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut packs = backpacks.iter().map(|pack| pack.iter().sum::<i64>());
+    let mut largest_three = [
+        packs.next().ok_or("Less than three backpacks")?,
+        packs.next().ok_or("Less than three backpacks")?,
+        packs.next().ok_or("Less than three backpacks")?,
+    ];
+    largest_three.sort();
+    for pack in packs {
+        if pack > largest_three[0] {
+            largest_three[0] = pack;
+            largest_three.sort();
+        }
     }
+    Ok(PuzzleResult::Numeric(largest_three.into_iter().sum()))
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    elves_backpacks(input)
-        .map_err(|err| err.into())
-        .and_then(|backpacks| {
-            backpacks
-                .into_iter()
-                .map(|pack| pack.into_iter().sum())
-                .max()
-                .ok_or(InputError::Empty.into())
-        })
-        .map(|v: i64| PuzzleResult::Numeric(v))
-}
+pub static SOLUTION: &dyn Day = &Puzzle;
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    elves_backpacks(input)
-        .map_err(|err| err.into())
-        .and_then(|backpacks| {
-            let mut packs = backpacks
-                .into_iter()
-                .map(|pack| pack.into_iter().sum::<i64>());
-            let mut largest_three = [
-                packs.next().ok_or(InputError::LessThanThree)?,
-                packs.next().ok_or(InputError::LessThanThree)?,
-                packs.next().ok_or(InputError::LessThanThree)?,
-            ];
-            largest_three.sort();
-            for pack in packs {
-                if pack > largest_three[0] {
-                    largest_three[0] = pack;
-                    largest_three.sort();
-                }
-            }
-            Ok(largest_three.into_iter().sum())
-        })
-        .map(|v: i64| PuzzleResult::Numeric(v))
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+
+    type Input<'a> = Vec<Vec<i64>>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(elves_backpacks(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
 }
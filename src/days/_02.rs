@@ -1,6 +1,6 @@
-use std::{error::Error, fmt::Display};
+use std::error::Error;
 
-use super::PuzzleResult;
+use super::{Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Debug, Clone, Copy)]
 enum RPSMove {
@@ -53,20 +53,7 @@ impl RPSMove {
     }
 }
 
-#[derive(Debug)]
-enum InputError {
-    NoSpace(String),
-    WrongMove(String),
-}
-
-impl Error for InputError {}
-impl Display for InputError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
-fn read_input(input: &str) -> Result<Vec<(RPSMove, Column2)>, InputError> {
+fn read_input(input: &str) -> Result<Vec<(RPSMove, Column2)>, ParseError> {
     Result::from_iter(input.split("\n").filter_map(|line| {
         let line = line.trim();
         if let Some((p1, p2)) = line.split_once(" ") {
@@ -77,27 +64,27 @@ fn read_input(input: &str) -> Result<Vec<(RPSMove, Column2)>, InputError> {
                 "A" => Rock,
                 "B" => Paper,
                 "C" => Scissor,
-                _ => return Some(Err(InputError::WrongMove(String::from(p1)))),
+                _ => return Some(Err(ParseError::at(p1, "A, B or C"))),
             };
             let p2 = match p2 {
                 "X" => X,
                 "Y" => Y,
                 "Z" => Z,
-                _ => return Some(Err(InputError::WrongMove(String::from(p2)))),
+                _ => return Some(Err(ParseError::at(p2, "X, Y or Z"))),
             };
             Some(Ok((p1, p2)))
         } else if line.trim() == "" {
             None // skip empty lines
         } else {
-            Some(Err(InputError::NoSpace(String::from(line))))
+            Some(Err(ParseError::at(line, "two moves separated by a space")))
         }
     }))
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    Ok(read_input(input)?
-        .into_iter()
-        .map(|(p2, p1)| {
+fn part1(rounds: &[(RPSMove, Column2)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    Ok(rounds
+        .iter()
+        .map(|&(p2, p1)| {
             let p1 = match p1 {
                 Column2::X => RPSMove::Rock,
                 Column2::Y => RPSMove::Paper,
@@ -109,10 +96,10 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     .map(|v: i64| PuzzleResult::Numeric(v))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    Ok(read_input(input)?
-        .into_iter()
-        .map(|(p2, p1)| {
+fn part2(rounds: &[(RPSMove, Column2)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    Ok(rounds
+        .iter()
+        .map(|&(p2, p1)| {
             use GameResult::*;
             use RPSMove::*;
             // converting into the needed result
@@ -136,3 +123,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         .sum())
     .map(|v: i64| PuzzleResult::Numeric(v))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    type Input<'a> = Vec<(RPSMove, Column2)>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(read_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
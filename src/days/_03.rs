@@ -1,21 +1,6 @@
-use std::{error::Error, fmt::Display};
+use std::error::Error;
 
-use super::PuzzleResult;
-
-#[derive(Debug)]
-enum InputError {
-    OddLenght(String),
-    NonThriceable,
-    StrangeChar(char),
-    NoCommon,
-}
-
-impl Error for InputError {}
-impl Display for InputError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
+use super::{Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Container(u64);
@@ -47,24 +32,26 @@ impl Container {
     }
 }
 impl TryFrom<&str> for Container {
-    type Error = InputError;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let mut res = Self(0);
-        for ch in value.chars() {
-            res = res.add(ch).ok_or(InputError::StrangeChar(ch))?
+        for (pos, ch) in value.char_indices() {
+            res = res
+                .add(ch)
+                .ok_or_else(|| ParseError::at(&value[pos..pos + ch.len_utf8()], "an item letter"))?
         }
         Ok(res)
     }
 }
 
-fn read_input_compartments(input: &str) -> Result<Vec<(Container, Container)>, InputError> {
+fn read_input_compartments(input: &str) -> Result<Vec<(Container, Container)>, ParseError> {
     Result::from_iter(input.split("\n").filter_map(|line| {
         let line = line.trim();
         if line == "" {
             None
         } else if line.chars().count() % 2 != 0 {
-            Some(Err(InputError::OddLenght(String::from(line))))
+            Some(Err(ParseError::at(line, "an even number of items")))
         } else {
             Some({
                 let (s1, s2) = line.split_at(line.chars().count() / 2);
@@ -73,18 +60,20 @@ fn read_input_compartments(input: &str) -> Result<Vec<(Container, Container)>, I
         }
     }))
 }
-fn read_input_groups(input: &str) -> Result<Vec<(Container, Container, Container)>, InputError> {
-    let lines: Vec<_> = Result::from_iter(input.split("\n").filter_map(|line| {
-        let line = line.trim();
-        if line == "" {
-            None
-        } else {
-            Some(Container::try_from(line))
-        }
-    }))?;
+fn read_input_groups(input: &str) -> Result<Vec<(Container, Container, Container)>, ParseError> {
+    let lines: Vec<_> = input
+        .split("\n")
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect();
     if lines.len() % 3 != 0 {
-        return Err(InputError::NonThriceable);
+        // pointing at the first rucksack of the incomplete group
+        return Err(ParseError::at(
+            lines[lines.len() - lines.len() % 3],
+            "a group of three rucksacks",
+        ));
     }
+    let lines: Vec<_> = Result::from_iter(lines.into_iter().map(Container::try_from))?;
     let groups = lines.len() / 3;
     let mut res = Vec::with_capacity(groups);
     let mut lines = lines.into_iter();
@@ -99,7 +88,7 @@ fn read_input_groups(input: &str) -> Result<Vec<(Container, Container, Container
     Ok(res)
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
+fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     let input = read_input_compartments(input)?;
     let mut total = 0;
     for (c1, c2) in input {
@@ -107,13 +96,13 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         if !common.empty() {
             total += common.first_priority() as i64;
         } else {
-            return Err(InputError::NoCommon.into());
+            return Err("No item is in both compartments".into());
         }
     }
     Ok(total).map(|v: i64| PuzzleResult::Numeric(v))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
+fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     let input = read_input_groups(input)?;
     let mut total = 0;
     for (c1, c2, c3) in input {
@@ -121,8 +110,23 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         if !common.empty() {
             total += common.first_priority() as i64;
         } else {
-            return Err(InputError::NoCommon.into());
+            return Err("No item is common to the group".into());
         }
     }
     Ok(total).map(|v: i64| PuzzleResult::Numeric(v))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+
+    /// The two parts split the rucksacks differently, so each one reads them on its own
+    type Input<'a> = &'a str;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
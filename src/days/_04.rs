@@ -1,25 +1,6 @@
-use std::{error::Error, fmt::Display, num::ParseIntError};
+use std::error::Error;
 
-use super::PuzzleResult;
-
-#[derive(Debug)]
-enum InputError {
-    NoComma,
-    NoLine,
-    ParseIntError(ParseIntError),
-}
-
-impl Error for InputError {}
-impl Display for InputError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-impl From<ParseIntError> for InputError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseIntError(value)
-    }
-}
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Debug, Clone, Copy)]
 struct SectionRange(i64, i64);
@@ -34,20 +15,22 @@ impl SectionRange {
     }
 }
 impl TryFrom<&str> for SectionRange {
-    type Error = InputError;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (s1, s2) = value.split_once("-").ok_or(InputError::NoLine)?;
-        let begin = s1.trim().parse()?;
-        let end = s2.trim().parse()?;
+        let (s1, s2) = value
+            .split_once("-")
+            .ok_or_else(|| ParseError::at(value, "a range of sections"))?;
+        let begin = parse_at(s1.trim(), "a section number")?;
+        let end = parse_at(s2.trim(), "a section number")?;
         Ok(Self(begin, end))
     }
 }
 
-fn read_input(input: &str) -> Result<Vec<(SectionRange, SectionRange)>, InputError> {
+fn read_input(input: &str) -> Result<Vec<(SectionRange, SectionRange)>, ParseError> {
     Result::from_iter(input.trim().lines().map(|line| {
         line.split_once(",")
-            .ok_or(InputError::NoComma)
+            .ok_or_else(|| ParseError::at(line, "two ranges separated by a comma"))
             .and_then(|(s1, s2)| {
                 SectionRange::try_from(s1)
                     .and_then(|s1| SectionRange::try_from(s2).map(|s2| (s1, s2)))
@@ -55,18 +38,29 @@ fn read_input(input: &str) -> Result<Vec<(SectionRange, SectionRange)>, InputErr
     }))
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    Ok(read_input(input)?
-        .into_iter()
+fn part1(pairs: &[(SectionRange, SectionRange)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    Ok(pairs
+        .iter()
         .filter(|(r1, r2)| r1.is_inside(*r2) || r2.is_inside(*r1))
         .count() as i64)
     .map(|v: i64| PuzzleResult::Numeric(v))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    Ok(read_input(input)?
-        .into_iter()
-        .filter(|(r1, r2)| r1.overlap(*r2))
-        .count() as i64)
-    .map(|v: i64| PuzzleResult::Numeric(v))
+fn part2(pairs: &[(SectionRange, SectionRange)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    Ok(pairs.iter().filter(|(r1, r2)| r1.overlap(*r2)).count() as i64)
+        .map(|v: i64| PuzzleResult::Numeric(v))
+}
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    type Input<'a> = Vec<(SectionRange, SectionRange)>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(read_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
 }
@@ -1,25 +1,9 @@
-use std::{error::Error, fmt::Display};
+use std::error::Error;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 // use regex::Regex;
 
-#[derive(Debug)]
-enum InputError {
-    NoBlankLine,
-    NoDrawing,
-    UnreadableMove(String),
-    StackEmptied,
-    StackEmptyAtTheEnd,
-}
-
-impl Error for InputError {}
-impl Display for InputError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 struct Move {
     quantity: u8,
@@ -27,11 +11,13 @@ struct Move {
     to: u8,
 }
 
-fn parse_drawing(input: &str) -> Result<Vec<Vec<char>>, InputError> {
+fn parse_drawing(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
     // splitting lines
     let (header, stacks_lines) = {
         let mut lines = input.lines().rev().filter(|l| l.trim() != "");
-        let header = lines.next().ok_or(InputError::NoDrawing)?;
+        let header = lines
+            .next()
+            .ok_or_else(|| ParseError::at(input, "a drawing of the stacks"))?;
         (header, lines.collect::<Vec<_>>())
     };
     // counting the number and position of the rows
@@ -41,6 +27,15 @@ fn parse_drawing(input: &str) -> Result<Vec<Vec<char>>, InputError> {
         .filter(|&(_, ch)| '1' <= ch && ch <= '9')
         .map(|(pos, ch)| (pos, ch.to_digit(10).unwrap() - 1))
         .collect();
+    for (idx, &(pos, col)) in col_pos.iter().enumerate() {
+        if col as usize != idx {
+            let (start, ch) = header.char_indices().nth(pos).unwrap();
+            return Err(ParseError::at(
+                &header[start..start + ch.len_utf8()],
+                format!("stack {}", idx + 1),
+            ));
+        }
+    }
     // making the stacks
     let mut stacks: Vec<Vec<char>> = (0..col_pos.len())
         .map(|_| Vec::with_capacity(stacks_lines.len()))
@@ -58,7 +53,7 @@ fn parse_drawing(input: &str) -> Result<Vec<Vec<char>>, InputError> {
 
     Ok(stacks)
 }
-fn parse_moves(input: &str) -> Result<Vec<Move>, InputError> {
+fn parse_moves(input: &str, stacks: usize) -> Result<Vec<Move>, ParseError> {
     /*
         // This is a cleaner REGEX solution. Sadly, also a lot slower
 
@@ -67,7 +62,7 @@ fn parse_moves(input: &str) -> Result<Vec<Move>, InputError> {
         }
         Result::from_iter(input.trim().lines().map(|line| {
             RE.captures(line)
-                .ok_or(InputError::UnreadableMove(line.to_string()))
+                .ok_or_else(|| ParseError::at(line, "a move"))
                 .map(|m| Move {
                     quantity: m.get(1).unwrap().as_str().parse().unwrap(),
                     from: m.get(2).unwrap().as_str().parse::<u8>().unwrap() - 1,
@@ -76,75 +71,90 @@ fn parse_moves(input: &str) -> Result<Vec<Move>, InputError> {
         }))
     */
     Result::from_iter(input.trim().lines().map(|line| {
-        line.trim()
+        let line = line.trim();
+        let (qt, from, to) = line
             // removing move
             .strip_prefix("move")
             // splitting parts
             .and_then(|line| line.split_once("from"))
             .and_then(|(qt, mov)| mov.split_once("to").map(|(from, to)| (qt, from, to)))
-            // converting into ints
-            .and_then(|(qt, from, to)| qt.trim().parse::<u8>().ok().map(|qt| (qt, from, to)))
-            .and_then(|(qt, from, to)| {
-                from.trim()
-                    .parse::<u8>()
-                    .ok()
-                    .map(|from| (qt, from - 1, to))
-            })
-            .and_then(|(qt, from, to)| to.trim().parse::<u8>().ok().map(|to| (qt, from, to - 1)))
-            // mapping to proprietary type
-            .map(|(quantity, from, to)| Move { quantity, from, to })
-            .ok_or(InputError::UnreadableMove(String::from(line)))
+            .ok_or_else(|| ParseError::at(line, "a move like \"move 1 from 2 to 3\""))?;
+        // stacks are numbered from 1
+        let stack = |number: &str| match parse_at::<u8>(number.trim(), "a stack number")? {
+            number @ 1.. if usize::from(number) <= stacks => Ok(number - 1),
+            _ => Err(ParseError::at(
+                number.trim(),
+                format!("a stack between 1 and {stacks}"),
+            )),
+        };
+        Ok(Move {
+            quantity: parse_at(qt.trim(), "a quantity")?,
+            from: stack(from)?,
+            to: stack(to)?,
+        })
     }))
 }
 
-fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<Move>), InputError> {
+fn parse_input(input: &str) -> Result<(Vec<Vec<char>>, Vec<Move>), ParseError> {
     // splitting the drawing from the move set
-    let (drawing, moves) = input.split_once("\n\n").ok_or(InputError::NoBlankLine)?;
+    let (drawing, moves) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::at(&input[input.len()..], "a blank line after the drawing"))?;
     let drawing = parse_drawing(drawing)?;
-    let moves = parse_moves(moves)?;
+    let moves = parse_moves(moves, drawing.len())?;
     Ok((drawing, moves))
 }
 
-fn stack_tops(stacks: Vec<Vec<char>>) -> Result<String, InputError> {
+fn stack_tops(stacks: Vec<Vec<char>>) -> Result<String, Box<dyn Error>> {
     Result::from_iter(stacks.into_iter().map(|stack| {
         stack
             .last()
             .map(|ch| *ch)
-            .ok_or(InputError::StackEmptyAtTheEnd)
+            .ok_or("A stack is empty at the end".into())
     }))
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let (mut stacks, moves) = parse_input(input)?;
+fn part1((stacks, moves): &(Vec<Vec<char>>, Vec<Move>)) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut stacks = stacks.clone();
     for mov in moves {
         for _ in 0..mov.quantity {
             let item = stacks[mov.from as usize]
                 .pop()
-                .ok_or(InputError::StackEmptied)?;
+                .ok_or("Moving from an empty stack")?;
             stacks[mov.to as usize].push(item)
         }
     }
-    stack_tops(stacks)
-        .map(|s| PuzzleResult::Textual(s))
-        .map_err(|e| e.into())
+    stack_tops(stacks).map(|s| PuzzleResult::Textual(s))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let (mut stacks, moves) = parse_input(input)?;
+fn part2((stacks, moves): &(Vec<Vec<char>>, Vec<Move>)) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut stacks = stacks.clone();
     for mov in moves {
         let mut items = vec![];
         for _ in 0..mov.quantity {
             items.push(
                 stacks[mov.from as usize]
                     .pop()
-                    .ok_or(InputError::StackEmptied)?,
+                    .ok_or("Moving from an empty stack")?,
             )
         }
         while let Some(item) = items.pop() {
             stacks[mov.to as usize].push(item)
         }
     }
-    stack_tops(stacks)
-        .map(|s| PuzzleResult::Textual(s))
-        .map_err(|e| e.into())
+    stack_tops(stacks).map(|s| PuzzleResult::Textual(s))
+}
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    type Input<'a> = (Vec<Vec<char>>, Vec<Move>);
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(parse_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
 }
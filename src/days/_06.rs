@@ -1,9 +1,8 @@
 use std::error::Error;
 
-use super::PuzzleResult;
+use super::{Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input: Vec<_> = input.chars().collect();
+fn part1(input: &[char]) -> Result<PuzzleResult, Box<dyn Error>> {
     const WINDOW: usize = 4;
     'outer: for (pos, window) in input.windows(WINDOW).enumerate() {
         for i in 0..window.len() {
@@ -18,8 +17,7 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     Err("Marker not found".into())
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input: Vec<_> = input.chars().collect();
+fn part2(input: &[char]) -> Result<PuzzleResult, Box<dyn Error>> {
     const WINDOW: usize = 14;
     'outer: for (pos, window) in input.windows(WINDOW).enumerate() {
         for i in 0..window.len() {
@@ -33,3 +31,24 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     }
     Err("Marker not found".into())
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+
+    type Input<'a> = Vec<char>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        let input = input.trim();
+        Ok(Result::from_iter(input.char_indices().map(|(idx, ch)| {
+            if ch.is_ascii_lowercase() {
+                Ok(ch)
+            } else {
+                Err(ParseError::at(&input[idx..idx + ch.len_utf8()], "a letter"))
+            }
+        }))?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, error::Error};
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Param, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Debug)]
 enum Command<'a> {
@@ -18,7 +18,7 @@ fn parse_input<'a>(input: &'a str) -> Result<Vec<Command<'a>>, Box<dyn Error>> {
     // removing the starting "$"
     let input = input
         .strip_prefix("$")
-        .ok_or("Input must start with a command")?;
+        .ok_or_else(|| ParseError::at(input.lines().next().unwrap_or(input), "a command"))?;
     // splitting commands
     let mut commands = Vec::new();
     for command in input.split("\n$") {
@@ -30,7 +30,7 @@ fn parse_input<'a>(input: &'a str) -> Result<Vec<Command<'a>>, Box<dyn Error>> {
         commands.push(match command.trim() {
             "ls" => {
                 if arg.trim() != "" {
-                    return Err("Unexpected arg to ls".into());
+                    return Err(ParseError::at(arg.trim(), "no argument to ls").into());
                 }
                 let mut files = vec![];
                 let mut dirs = vec![];
@@ -39,20 +39,17 @@ fn parse_input<'a>(input: &'a str) -> Result<Vec<Command<'a>>, Box<dyn Error>> {
                         if p1.trim() == "dir" {
                             dirs.push(name.trim())
                         } else {
-                            match p1.parse::<usize>() {
-                                Ok(size) => files.push((name.trim(), size)),
-                                Err(err) => return Err(err.into()),
-                            }
+                            files.push((name.trim(), parse_at(p1, "dir or a file size")?))
                         }
                     } else {
-                        return Err("No space in ls output line".into());
+                        return Err(ParseError::at(line, "a dir or a file with its size").into());
                     };
                 }
                 Command::Ls(dirs, files)
             }
             "cd" => {
                 if output.trim() != "" {
-                    return Err("Unexpected output to cd".into());
+                    return Err(ParseError::at(output.trim(), "no output from cd").into());
                 }
                 Command::Cd(match arg.trim() {
                     "/" => CdDest::Root,
@@ -60,13 +57,13 @@ fn parse_input<'a>(input: &'a str) -> Result<Vec<Command<'a>>, Box<dyn Error>> {
                     dest => CdDest::Child(dest),
                 })
             }
-            cmd => return Err(format!("Unknow command {}", cmd).into()),
+            cmd => return Err(ParseError::at(cmd, "ls or cd").into()),
         })
     }
     Ok(commands)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Directory {
     subdirs: HashMap<String, Directory>,
     files: HashMap<String, usize>,
@@ -163,29 +160,59 @@ fn build_directory_tree(mut history: Vec<Command<'_>>) -> Result<Directory, Box<
     Ok(current_path.pop().unwrap().1)
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let history = parse_input(input)?;
-    let mut root_dir = build_directory_tree(history)?;
+/// Parameters of the puzzle
+struct PuzzleParams {
+    /// Maximum size of the directories counted in the first part
+    small_dir_size: usize,
+    /// Total space on the disk
+    disk_size: usize,
+    /// Free space needed by the update
+    needed_space: usize,
+}
+impl PuzzleParams {
+    fn new(params: &Params) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            small_dir_size: params.get("small_dir_size")?,
+            disk_size: params.get("disk_size")?,
+            needed_space: params.get("needed_space")?,
+        })
+    }
+}
+
+fn part1(
+    (root_dir, PuzzleParams { small_dir_size, .. }): &(Directory, PuzzleParams),
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut root_dir = root_dir.clone();
 
     let mut total = 0;
     root_dir.walk_subdirs(&mut |dir| {
         let size = dir.cached_size();
-        if size <= 100000 {
+        if size <= *small_dir_size {
             total += size
         }
     });
     Ok(PuzzleResult::Numeric(total as i64))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let history = parse_input(input)?;
-    let mut root_dir = build_directory_tree(history)?;
+fn part2(
+    (
+        root_dir,
+        PuzzleParams {
+            disk_size,
+            needed_space,
+            ..
+        },
+    ): &(Directory, PuzzleParams),
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut root_dir = root_dir.clone();
 
-    const MAX_ROOT_SIZE: usize = 70000000 - 30000000;
-    if root_dir.cached_size() < MAX_ROOT_SIZE {
+    let max_root_size = disk_size
+        .checked_sub(*needed_space)
+        .ok_or("The update does not fit on the disk")?;
+    if root_dir.cached_size() < max_root_size {
         return Err("Root is already small enough".into());
     }
-    let delete_threshold = root_dir.cached_size() - MAX_ROOT_SIZE;
+    let delete_threshold = root_dir.cached_size() - max_root_size;
 
     let mut smallest = root_dir.cached_size();
     root_dir.walk_subdirs(&mut |dir| {
@@ -196,3 +223,35 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     });
     Ok(PuzzleResult::Numeric(smallest as i64))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "small_dir_size",
+            default: "100000",
+            description: "Maximum size of the directories counted in the first part",
+        },
+        Param {
+            name: "disk_size",
+            default: "70000000",
+            description: "Total space on the disk",
+        },
+        Param {
+            name: "needed_space",
+            default: "30000000",
+            description: "Free space needed by the update",
+        },
+    ];
+
+    type Input<'a> = (Directory, PuzzleParams);
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        let root_dir = build_directory_tree(parse_input(input)?)?;
+        Ok((root_dir, PuzzleParams::new(params)?))
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
@@ -1,92 +1,16 @@
-use std::{
-    error::Error,
-    ops::{Index, IndexMut},
-};
+use std::error::Error;
 
-use super::PuzzleResult;
-
-struct Grid<T> {
-    height: usize,
-    width: usize,
-    memory: Box<[T]>,
-}
-
-impl<T> Grid<T> {
-    fn get(&self, x: usize, y: usize) -> Option<&T> {
-        if x < self.width && y < self.height {
-            Some(&self.memory[y * self.width + x])
-        } else {
-            None
-        }
-    }
-    fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
-        if x < self.width && y < self.height {
-            Some(&mut self.memory[y * self.width + x])
-        } else {
-            None
-        }
-    }
-
-    fn shape(&self) -> (usize, usize) {
-        (self.width, self.height)
-    }
-
-    fn iter(&self) -> impl Iterator<Item = &T> {
-        self.memory.iter()
-    }
-}
-
-impl<T> Grid<T>
-where
-    T: Clone,
-{
-    fn new(height: usize, width: usize, fill: T) -> Self {
-        Self {
-            height,
-            width,
-            memory: vec![fill; height * width].into_boxed_slice(),
-        }
-    }
-    fn new_like<O>(other: &Grid<O>, fill: T) -> Self {
-        Self::new(other.height, other.width, fill)
-    }
-}
-impl<T> Index<(usize, usize)> for Grid<T> {
-    type Output = T;
-
-    fn index(&self, index: (usize, usize)) -> &Self::Output {
-        self.get(index.0, index.1)
-            .unwrap_or_else(|| panic!("Index ({},{}) is out of range!", index.0, index.1))
-    }
-}
-impl<T> IndexMut<(usize, usize)> for Grid<T> {
-    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
-        self.get_mut(index.0, index.1)
-            .unwrap_or_else(|| panic!("Index ({},{}) is out of range!", index.0, index.1))
-    }
-}
+use super::{grid::Grid, Day, Params, PartFn, PuzzleResult, Solution};
 
 fn parse_input(input: &str) -> Result<Grid<u8>, Box<dyn Error>> {
-    let lines: Vec<_> = input.trim().lines().collect();
-    let height = lines.len();
-    let width = lines.iter().map(|l| l.len()).max().unwrap_or(0);
-
-    let mut grid = Grid::new(height, width, 0);
-    for (y, line) in lines.into_iter().rev().enumerate() {
-        for (x, ch) in line.chars().enumerate() {
-            grid[(x, y)] = ch.to_digit(10).ok_or_else(|| -> Box<dyn Error> {
-                format!("{ch} is not a valid height!").into()
-            })? as u8;
-        }
-    }
-
-    Ok(grid)
+    Ok(Grid::parse(input.trim(), 0, "a tree height", |_, ch| {
+        ch.to_digit(10).map(|h| h as u8)
+    })?)
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let heights = parse_input(input)?;
-    let (max_height, max_width) = heights.shape();
-    let mut visible = Grid::new_like(&heights, false);
+fn part1(heights: &Grid<u8>) -> Result<PuzzleResult, Box<dyn Error>> {
+    let (max_width, max_height) = heights.shape();
+    let mut visible = Grid::new_like(heights, false);
 
     for x in 0..max_width {
         // top -> down
@@ -134,9 +58,8 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     ))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let heights = parse_input(input)?;
-    let (max_heigth, max_width) = heights.shape();
+fn part2(heights: &Grid<u8>) -> Result<PuzzleResult, Box<dyn Error>> {
+    let (max_width, max_heigth) = heights.shape();
 
     let mut max_scenic_score = 0;
     for tx in 0..max_width {
@@ -201,3 +124,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     }
     Ok(PuzzleResult::Numeric(max_scenic_score as i64))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+
+    type Input<'a> = Grid<u8>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
@@ -1,6 +1,6 @@
-use std::{collections::HashSet, error::Error, fmt::Display, num::ParseIntError};
+use std::{collections::HashSet, error::Error};
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Clone, Copy)]
 enum Direction {
@@ -76,51 +76,32 @@ impl<const LEN: usize> Rope<LEN> {
     }
 }
 
-#[derive(Debug, Clone)]
-enum ParseError {
-    MissingSpace(String),
-    UnknowDirection(String),
-    ParseIntError(ParseIntError),
-}
-impl Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-impl Error for ParseError {}
-
 fn parse_input(input: &str) -> Result<Vec<(Direction, usize)>, ParseError> {
     Result::from_iter(input.trim().lines().map(|line| {
         line.split_once(" ")
-            .ok_or_else(|| ParseError::MissingSpace(line.to_owned()))
-            .and_then(|(p1, p2)| {
-                p2.trim()
-                    .parse::<usize>()
-                    .map(|p2| (p1, p2))
-                    .map_err(|err| ParseError::ParseIntError(err))
-            })
+            .ok_or_else(|| ParseError::at(line, "a direction and a distance"))
+            .and_then(|(p1, p2)| parse_at::<usize>(p2.trim(), "a distance").map(|p2| (p1, p2)))
             .and_then(|(p1, p2)| {
                 let dir = match p1 {
                     "U" => Direction::Up,
                     "D" => Direction::Down,
                     "L" => Direction::Left,
                     "R" => Direction::Right,
-                    dir => return Err(ParseError::UnknowDirection(dir.to_owned())),
+                    dir => return Err(ParseError::at(dir, "U, D, L or R")),
                 };
                 Ok((dir, p2))
             })
     }))
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let directions = parse_input(input)?;
+fn part1(directions: &[(Direction, usize)]) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut head = (0, 0);
     let mut chain = Link { tail: head };
 
     let mut tail_positions = HashSet::new();
     tail_positions.insert(chain.tail);
 
-    for (direction, times) in directions {
+    for &(direction, times) in directions {
         let direction = direction.delta();
         for _ in 0..times {
             head.0 += direction.0;
@@ -133,15 +114,14 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     Ok(PuzzleResult::Numeric(tail_positions.len() as i64))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let directions = parse_input(input)?;
+fn part2(directions: &[(Direction, usize)]) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut head = (0, 0);
     let mut chain: Rope<9> = Rope::new();
 
     let mut tail_positions = HashSet::new();
     tail_positions.insert(chain.tail());
 
-    for (direction, times) in directions {
+    for &(direction, times) in directions {
         let direction = direction.delta();
         for _ in 0..times {
             head.0 += direction.0;
@@ -153,3 +133,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 
     Ok(PuzzleResult::Numeric(tail_positions.len() as i64))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Rope Bridge";
+
+    type Input<'a> = Vec<(Direction, usize)>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(parse_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -1,6 +1,6 @@
-use std::{error::Error, fmt::Display, num::ParseIntError};
+use std::error::Error;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Clone, Copy)]
 enum Instruction {
@@ -16,20 +16,6 @@ impl Instruction {
     }
 }
 
-#[derive(Debug, Clone)]
-enum ParseError {
-    UnknowInstruction(String),
-    UnexpectedArgument(String),
-    ParseIntError(ParseIntError),
-    MissingArgument,
-}
-impl Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
-    }
-}
-impl Error for ParseError {}
-
 fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
     Result::from_iter(input.trim().lines().map(|line| {
         let (instr, arg) = line
@@ -38,13 +24,10 @@ fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
             .unwrap_or((line, None));
         match (instr, arg) {
             ("noop", None) => Ok(Instruction::Noop),
-            ("noop", Some(a)) => Err(ParseError::UnexpectedArgument(a.to_owned())),
-            ("addx", Some(a)) => a
-                .parse()
-                .map(|v| Instruction::AddX(v))
-                .map_err(|err| ParseError::ParseIntError(err)),
-            ("addx", None) => Err(ParseError::MissingArgument),
-            (instr, _) => Err(ParseError::UnknowInstruction(instr.to_owned())),
+            ("noop", Some(a)) => Err(ParseError::at(a, "the end of the line")),
+            ("addx", Some(a)) => parse_at(a, "a number").map(|v| Instruction::AddX(v)),
+            ("addx", None) => Err(ParseError::at(&line[line.len()..], "a number")),
+            (instr, _) => Err(ParseError::at(instr, "noop or addx")),
         }
     }))
 }
@@ -94,9 +77,8 @@ impl CRT {
     }
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input = parse_input(input)?;
-    let mut crt = CRT::new(input);
+fn part1(program: &[Instruction]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut crt = CRT::new(program.to_vec());
     let mut total = 0;
     while let Some(x) = crt.step() {
         let stregth = (crt.clock as isize) * x;
@@ -111,9 +93,8 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     Ok(PuzzleResult::Numeric(total as i64))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input = parse_input(input)?;
-    let mut crt = CRT::new(input);
+fn part2(program: &[Instruction]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut crt = CRT::new(program.to_vec());
     let mut screen = String::with_capacity(40 * 6);
     while let Some(sprite_pos) = crt.step() {
         let cycle = crt.clock;
@@ -133,3 +114,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     }
     Ok(PuzzleResult::AsciiArt(screen))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+
+    type Input<'a> = Vec<Instruction>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(parse_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -1,13 +1,12 @@
 use regex::Regex;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 use std::{
-    collections::{hash_map::RandomState, BinaryHeap, HashSet, VecDeque},
+    collections::{BinaryHeap, VecDeque},
     error::Error,
-    fmt::Display,
     str::FromStr,
 };
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Operation {
     Add(usize),
     Mul(usize),
@@ -22,7 +21,7 @@ impl Operation {
         }
     }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct MonkeyData {
     items: VecDeque<usize>,
     operation: Operation,
@@ -66,7 +65,7 @@ impl MonkeyData {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Monkeys {
     monkeys: Vec<MonkeyData>,
     modulus: usize,
@@ -112,100 +111,103 @@ impl FromStr for Monkeys {
         lazy_static! {
             static ref MONKEY_RE: Regex = Regex::new(r"Monkey\s+(?P<num>\d+)\s*:\s+Starting items\s*:\s*(?P<items>\d+(?:\s*,\s*\d+)*)\s+Operation\s*:\s*new\s*=\s*old\s*(?P<op>[*+])\s*(?P<op_value>\d+|old)\s+Test\s*:\s*divisible by\s+(?P<test_divisor>\d+)\s+If true\s*:\sthrow to monkey\s+(?P<throw_if_true>\d+)\s+If false\s*:\sthrow to monkey\s+(?P<throw_if_false>\d+)").unwrap();
         }
+        // checking nothing is left between the monkeys
+        let mut last_end = 0;
+        for found in MONKEY_RE
+            .find_iter(s)
+            .map(|m| (m.start(), m.end()))
+            .chain([(s.len(), s.len())])
+        {
+            let between = s[last_end..found.0].trim();
+            if !between.is_empty() {
+                return Err(ParseError::at(between, "the description of a monkey"));
+            }
+            last_end = found.1
+        }
         // collecting monkey regex matches
-        let mut monkeys: Vec<(usize, _)> = MONKEY_RE
-            .captures_iter(s)
-            .map(|capture| {
-                (
-                    capture.name("num").unwrap().as_str().parse().unwrap(),
+        let mut targets = vec![];
+        let mut monkeys: Vec<(&str, _)> = Result::from_iter(MONKEY_RE.captures_iter(s).map(
+            |capture| -> Result<_, ParseError> {
+                let field = |name: &str| capture.name(name).unwrap().as_str();
+                let test_divisor = field("test_divisor");
+                targets.extend([field("throw_if_true"), field("throw_if_false")]);
+                Ok((
+                    field("num"),
                     MonkeyData {
-                        items: capture
-                            .name("items")
-                            .unwrap()
-                            .as_str()
-                            .split(",")
-                            .map(|v| v.trim().parse().unwrap())
-                            .collect(),
-                        operation: match (
-                            capture.name("op").unwrap().as_str(),
-                            capture.name("op_value").unwrap().as_str(),
-                        ) {
-                            ("+", v) => Operation::Add(v.parse().unwrap()),
+                        items: Result::from_iter(
+                            field("items")
+                                .split(",")
+                                .map(|v| parse_at(v.trim(), "a worry level")),
+                        )?,
+                        operation: match (field("op"), field("op_value")) {
+                            ("+", v) => Operation::Add(parse_at(v, "a number")?),
                             ("*", "old") => Operation::Square,
-                            ("*", v) => Operation::Mul(v.parse().unwrap()),
+                            ("*", v) => Operation::Mul(parse_at(v, "a number")?),
                             _ => unreachable!(),
                         },
-                        test_divisor: capture
-                            .name("test_divisor")
-                            .unwrap()
-                            .as_str()
-                            .parse()
-                            .unwrap(),
-                        throw_if_true: capture
-                            .name("throw_if_true")
-                            .unwrap()
-                            .as_str()
-                            .parse()
-                            .unwrap(),
-                        throw_if_false: capture
-                            .name("throw_if_false")
-                            .unwrap()
-                            .as_str()
-                            .parse()
-                            .unwrap(),
+                        test_divisor: match parse_at(test_divisor, "a divisor")? {
+                            0 => return Err(ParseError::at(test_divisor, "a non zero divisor")),
+                            divisor => divisor,
+                        },
+                        throw_if_true: parse_at(field("throw_if_true"), "a monkey number")?,
+                        throw_if_false: parse_at(field("throw_if_false"), "a monkey number")?,
                         inspections: 0,
                     },
-                )
-            })
-            .collect();
+                ))
+            },
+        ))?;
         // sorting monkeys
-        monkeys.sort_by_key(|(v, _)| *v);
+        let number = |num: &str| parse_at::<usize>(num, "a monkey number");
+        for (num, _) in monkeys.iter() {
+            number(num)?;
+        }
+        monkeys.sort_by_key(|(v, _)| v.parse::<usize>().unwrap());
         // checking all monkey are accounted for
-        if monkeys
-            .iter()
-            .enumerate()
-            .all(|(idx, (mnum, _))| idx == *mnum)
-        {
-            // discarding indices
-            let monkeys: Vec<_> = monkeys.into_iter().map(|(_, m)| m).collect();
-            // calculating modulus
-            let modulus = monkeys.iter().map(|m| m.test_divisor).product();
-            Ok(Self { monkeys, modulus })
-        } else {
-            let monkeys: HashSet<_, RandomState> =
-                HashSet::from_iter(monkeys.into_iter().map(|(v, _)| v));
-            let missing = HashSet::from_iter(0..=*monkeys.iter().max().unwrap())
-                .difference(&monkeys)
-                .map(|v| *v)
-                .collect();
-            Err(ParseError::MissingMonkeys(missing))
+        for (idx, (mnum, _)) in monkeys.iter().enumerate() {
+            if number(mnum)? != idx {
+                return Err(ParseError::at(mnum, format!("monkey {idx}")));
+            }
         }
+        // checking the monkeys throw to each other
+        for target in targets {
+            if number(target)? >= monkeys.len() {
+                return Err(ParseError::at(target, "the number of a monkey"));
+            }
+        }
+        // discarding indices
+        let monkeys: Vec<_> = monkeys.into_iter().map(|(_, m)| m).collect();
+        // calculating modulus
+        let modulus = monkeys.iter().map(|m| m.test_divisor).product();
+        Ok(Self { monkeys, modulus })
     }
 }
 
-#[derive(Debug)]
-enum ParseError {
-    MissingMonkeys(Vec<usize>),
-}
-impl Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self)
-    }
-}
-impl Error for ParseError {}
-
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let mut monkeys: Monkeys = input.parse()?;
+fn part1(monkeys: &Monkeys) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut monkeys = monkeys.clone();
     for _ in 0..20 {
         monkeys.round()
     }
     Ok(PuzzleResult::Numeric(monkeys.monkey_business() as i64))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let mut monkeys: Monkeys = input.parse()?;
+fn part2(monkeys: &Monkeys) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut monkeys = monkeys.clone();
     for _ in 0..10000 {
         monkeys.round2()
     }
     Ok(PuzzleResult::Numeric(monkeys.monkey_business() as i64))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+
+    type Input<'a> = Monkeys;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(input.parse()?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
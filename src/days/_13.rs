@@ -1,5 +1,5 @@
-use super::PuzzleResult;
-use std::{cmp::Ordering, error::Error, iter::Peekable};
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
+use std::{cmp::Ordering, error::Error};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 enum Item {
@@ -43,53 +43,47 @@ impl Ord for Item {
         unreachable!()
     }
 }
+/// The first char of `s`, where an error is reported
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
 impl Item {
-    fn try_from_chars<ChIter>(chs: &mut Peekable<ChIter>) -> Result<Self, String>
-    where
-        ChIter: Iterator<Item = char>,
-    {
-        match chs.peek() {
-            Some('[') => {
-                chs.next();
-                if let Some(']') = chs.peek() {
-                    chs.next();
-                    Ok(Self::List(vec![]))
-                } else {
-                    let mut items = vec![Self::try_from_chars(chs)?];
-                    while let Some(',') = chs.peek() {
-                        chs.next();
-                        items.push(Self::try_from_chars(chs)?);
-                    }
-                    if let Some(']') = chs.next() {
-                        Ok(Self::List(items))
-                    } else {
-                        Err("Expected ]".to_owned())
-                    }
-                }
+    /// Parse the item at the start of `s`, giving it with the rest of `s`
+    fn parse_prefix(s: &str) -> Result<(Self, &str), ParseError> {
+        if let Some(mut rest) = s.strip_prefix('[') {
+            let mut items = vec![];
+            if let Some(rest) = rest.strip_prefix(']') {
+                return Ok((Self::List(items), rest));
             }
-            Some(ch) if ch.is_digit(10) => {
-                let mut digits = String::new();
-                while chs.peek().is_some_and(|ch| ch.is_digit(10)) {
-                    digits.push(chs.next().unwrap())
+            loop {
+                let (item, after) = Self::parse_prefix(rest)?;
+                items.push(item);
+                if let Some(after) = after.strip_prefix(',') {
+                    rest = after
+                } else if let Some(after) = after.strip_prefix(']') {
+                    return Ok((Self::List(items), after));
+                } else {
+                    return Err(ParseError::at(first_char(after), "a comma or ]"));
                 }
-                let n = digits.parse::<usize>().map_err(|err| err.to_string())?;
-                Ok(Self::Num(n))
             }
-            Some(ch) => Err(format!("Unexpected char {ch:?}")),
-            None => Err("Expected Items".to_owned()),
         }
+        let digits = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+        if digits == 0 {
+            return Err(ParseError::at(first_char(s), "a number or a list"));
+        }
+        Ok((Self::Num(parse_at(&s[..digits], "a number")?), &s[digits..]))
     }
 }
 impl TryFrom<&str> for Item {
-    type Error = String;
+    type Error = ParseError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let chs = &mut value.chars().peekable();
-        let res = Self::try_from_chars(chs)?;
-        if let None = chs.next() {
+        let (res, rest) = Self::parse_prefix(value)?;
+        if rest.is_empty() {
             Ok(res)
         } else {
-            Err("String not consumed".to_owned())
+            Err(ParseError::at(rest, "the end of the line"))
         }
     }
 }
@@ -97,7 +91,7 @@ impl TryFrom<&str> for Item {
 fn parse_input(input: &str) -> Result<Vec<(Item, Item)>, Box<dyn Error>> {
     Result::from_iter(input.trim().split("\n\n").map(|pair| {
         pair.split_once("\n")
-            .ok_or("Missing newline".to_owned())
+            .ok_or_else(|| ParseError::at(pair, "a pair of packets"))
             .and_then(|(p1, p2)| {
                 Item::try_from(p1).and_then(|p1| Item::try_from(p2).map(|p2| (p1, p2)))
             })
@@ -105,28 +99,21 @@ fn parse_input(input: &str) -> Result<Vec<(Item, Item)>, Box<dyn Error>> {
     .map_err(|err| err.into())
 }
 
-fn parse_input_2(input: &str) -> Result<Vec<Item>, Box<dyn Error>> {
-    Result::from_iter(
-        input
-            .split("\n")
-            .map(|line| line.trim())
-            .filter(|line| line.len() > 0)
-            .map(|line| line.try_into().map_err(|err: String| err.into())),
-    )
-}
-
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
+fn part1(pairs: &[(Item, Item)]) -> Result<PuzzleResult, Box<dyn Error>> {
     Ok(PuzzleResult::Numeric(
-        parse_input(input)?
-            .into_iter()
+        pairs
+            .iter()
             .enumerate()
             .filter_map(|(i, (p1, p2))| (p1 < p2).then_some(i + 1))
             .sum::<usize>() as i64,
     ))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let mut input = parse_input_2(input)?;
+fn part2(pairs: &[(Item, Item)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut input: Vec<_> = pairs
+        .iter()
+        .flat_map(|(p1, p2)| [p1.clone(), p2.clone()])
+        .collect();
     let marker_2 = Item::try_from("[[2]]").unwrap();
     let marker_6 = Item::try_from("[[6]]").unwrap();
     input.push(marker_2.clone());
@@ -137,3 +124,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
             * (input.binary_search(&marker_6).unwrap() + 1)) as i64,
     ))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Distress Signal";
+
+    type Input<'a> = Vec<(Item, Item)>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -1,98 +1,24 @@
-use std::{
-    error::Error,
-    fmt::Display,
-    num::ParseIntError,
-    ops::{Index, IndexMut},
-};
-
-use super::PuzzleResult;
-#[derive(Debug)]
-enum ParseError {
-    MissingComma,
-    ParseIntError(ParseIntError),
-}
-impl From<ParseIntError> for ParseError {
-    fn from(value: ParseIntError) -> Self {
-        Self::ParseIntError(value)
-    }
-}
-impl Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            ParseError::MissingComma => write!(f, "Missing comma"),
-            ParseError::ParseIntError(err) => write!(f, "{err}"),
-        }
-    }
-}
-impl Error for ParseError {}
+use std::error::Error;
+
+use super::{grid::Grid, parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 fn parse_input(input: &str) -> Result<Vec<Vec<(isize, isize)>>, ParseError> {
     Result::from_iter(input.trim().lines().map(|line| {
         Result::from_iter(line.split("->").map(|pos| {
             pos.split_once(',')
-                .ok_or(ParseError::MissingComma)
+                .ok_or_else(|| ParseError::at(pos.trim(), "a point like 498,4"))
                 .and_then(|(p1, p2)| {
-                    p1.trim()
-                        .parse::<isize>()
-                        .and_then(|p1| p2.trim().parse::<isize>().map(|p2| (p1, p2)))
-                        .map_err(|err| err.into())
+                    Ok((
+                        parse_at(p1.trim(), "a coordinate")?,
+                        parse_at(p2.trim(), "a coordinate")?,
+                    ))
                 })
         }))
     }))
 }
 
-struct Grid<T> {
-    height: usize,
-    width: usize,
-    memory: Box<[T]>,
-}
-
-impl<T> Grid<T> {
-    fn get(&self, x: isize, y: isize) -> Option<&T> {
-        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
-            Some(&self.memory[(y as usize) * self.width + x as usize])
-        } else {
-            None
-        }
-    }
-    fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
-        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
-            Some(&mut self.memory[(y as usize) * self.width + x as usize])
-        } else {
-            None
-        }
-    }
-}
-
-impl<T> Grid<T>
-where
-    T: Clone,
-{
-    fn new(width: usize, height: usize, fill: T) -> Self {
-        Self {
-            height,
-            width,
-            memory: vec![fill; height * width].into_boxed_slice(),
-        }
-    }
-}
-impl<T> Index<(isize, isize)> for Grid<T> {
-    type Output = T;
-
-    fn index(&self, index: (isize, isize)) -> &Self::Output {
-        self.get(index.0, index.1)
-            .unwrap_or_else(|| panic!("Index ({},{}) is out of range!", index.0, index.1))
-    }
-}
-impl<T> IndexMut<(isize, isize)> for Grid<T> {
-    fn index_mut(&mut self, index: (isize, isize)) -> &mut Self::Output {
-        self.get_mut(index.0, index.1)
-            .unwrap_or_else(|| panic!("Index ({},{}) is out of range!", index.0, index.1))
-    }
-}
-
 fn make_field(
-    input: Vec<Vec<(isize, isize)>>,
+    input: &[Vec<(isize, isize)>],
     drop_pos: (isize, isize),
     floor: bool,
 ) -> Result<(Grid<bool>, (isize, isize)), Box<dyn Error>> {
@@ -149,8 +75,7 @@ fn make_field(
     Ok((field, drop_pos))
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input = parse_input(input)?;
+fn part1(input: &[Vec<(isize, isize)>]) -> Result<PuzzleResult, Box<dyn Error>> {
     let drop_pos: (isize, isize) = (500, 0);
     let (mut field, drop_pos) = make_field(input, drop_pos, false)?;
 
@@ -163,9 +88,9 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         }
         'fall: loop {
             match (
-                field.get(pos.0 - 1, pos.1 + 1),
-                field.get(pos.0, pos.1 + 1),
-                field.get(pos.0 + 1, pos.1 + 1),
+                field.get((pos.0 - 1, pos.1 + 1)),
+                field.get((pos.0, pos.1 + 1)),
+                field.get((pos.0 + 1, pos.1 + 1)),
             ) {
                 (None, None, None) => break 'grains, // reached the bottom, fall infinitely
 
@@ -193,8 +118,7 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     Ok(PuzzleResult::Numeric(deposited_grains))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input = parse_input(input)?;
+fn part2(input: &[Vec<(isize, isize)>]) -> Result<PuzzleResult, Box<dyn Error>> {
     let drop_pos: (isize, isize) = (500, 0);
     let (mut field, drop_pos) = make_field(input, drop_pos, true)?;
 
@@ -207,9 +131,9 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         }
         'fall: loop {
             match (
-                field.get(pos.0 - 1, pos.1 + 1),
-                field.get(pos.0, pos.1 + 1),
-                field.get(pos.0 + 1, pos.1 + 1),
+                field.get((pos.0 - 1, pos.1 + 1)),
+                field.get((pos.0, pos.1 + 1)),
+                field.get((pos.0 + 1, pos.1 + 1)),
             ) {
                 (_, Some(false), _) => pos.1 += 1, // drop down
                 (Some(false), Some(true), _) => {
@@ -234,3 +158,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 
     Ok(PuzzleResult::Numeric(deposited_grains))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 14;
+    const TITLE: &'static str = "Regolith Reservoir";
+
+    type Input<'a> = Vec<Vec<(isize, isize)>>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(parse_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
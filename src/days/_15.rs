@@ -2,7 +2,7 @@ use std::{collections::HashSet, error::Error, fmt::Display, isize, iter::once};
 
 use regex::Regex;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Param, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 /// List of segments, non overlapping
 /// Segment are [.0,.1)
@@ -70,31 +70,35 @@ impl Display for Segments {
     }
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = ((isize, isize), (isize, isize))> + '_ {
+/// Position of a sensor and of its closest beacon
+type Report = ((isize, isize), (isize, isize));
+
+fn parse_input(input: &str) -> Result<Vec<Report>, ParseError> {
     lazy_static! {
         static ref LINE_RE: Regex = Regex::new(
-            r"(?m)^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$"
+            r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$"
         )
         .unwrap();
     }
-    LINE_RE
-        .captures_iter(input)
-        .map(|capture| -> ((isize, isize), (isize, isize)) {
-            (
-                (
-                    capture.get(1).unwrap().as_str().parse().unwrap(),
-                    capture.get(2).unwrap().as_str().parse().unwrap(),
-                ),
-                (
-                    capture.get(3).unwrap().as_str().parse().unwrap(),
-                    capture.get(4).unwrap().as_str().parse().unwrap(),
-                ),
-            )
-        })
+    let reports: Vec<Report> = Result::from_iter(input.trim().lines().map(|line| {
+        let line = line.trim();
+        let capture = LINE_RE
+            .captures(line)
+            .ok_or_else(|| ParseError::at(line, "a sensor and its closest beacon"))?;
+        let coord = |n: usize| parse_at(capture.get(n).unwrap().as_str(), "a coordinate");
+        Ok(((coord(1)?, coord(2)?), (coord(3)?, coord(4)?)))
+    }))?;
+    if reports.is_empty() {
+        return Err(ParseError::at(
+            input.trim(),
+            "a sensor and its closest beacon",
+        ));
+    }
+    Ok(reports)
 }
 
 fn find_radii(
-    sensors: impl Iterator<Item = ((isize, isize), (isize, isize))>,
+    sensors: impl Iterator<Item = Report>,
 ) -> impl Iterator<Item = ((isize, isize), isize)> {
     sensors.map(|(p1, p2)| (p1, manhattan(p1, p2)))
 }
@@ -103,18 +107,35 @@ fn manhattan(p1: (isize, isize), p2: (isize, isize)) -> isize {
     (p1.0 - p2.0).abs() + (p1.1 - p2.1).abs()
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    const LINE_Y: isize = 2000000;
+/// Parameters of the puzzle
+struct PuzzleParams {
+    /// Row where the first part counts the impossible positions
+    line_y: isize,
+    /// Side of the square where the distress beacon is searched
+    square_size: isize,
+}
+impl PuzzleParams {
+    fn new(params: &Params) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            line_y: params.get("line_y")?,
+            square_size: params.get("square_size")?,
+        })
+    }
+}
+
+fn part1(
+    &(ref reports, PuzzleParams { line_y, .. }): &(Vec<Report>, PuzzleParams),
+) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut impossible_segments = Segments::new();
     let mut beacons_on_line = HashSet::new();
-    for (sensor, beacon) in parse_input(input) {
+    for &(sensor, beacon) in reports {
         let radius = manhattan(sensor, beacon);
         // check if the beacon is on the line
-        if beacon.1 == LINE_Y {
+        if beacon.1 == line_y {
             beacons_on_line.insert(beacon.0);
         }
         // calculate the segment remaining
-        let residual_radius = radius - (sensor.1 - LINE_Y).abs();
+        let residual_radius = radius - (sensor.1 - line_y).abs();
         if residual_radius >= 0 {
             let segment = (sensor.0 - residual_radius, sensor.0 + residual_radius + 1);
             impossible_segments.add(segment);
@@ -127,9 +148,10 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 /// Solution with beacon border intersections
 /// We assume the solution is unique => is constrained by at least 2 sensors boder, or an angle.
 /// Also, we do all with a single call to laxy iterators chain. Cause no one should read this code
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    const SQUARE_SIZE: isize = 4000000;
-    let sensors: Vec<_> = find_radii(parse_input(input)).collect();
+fn part2(
+    &(ref reports, PuzzleParams { square_size, .. }): &(Vec<Report>, PuzzleParams),
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let sensors: Vec<_> = find_radii(reports.iter().copied()).collect();
 
     // find all papable points
     sensors[..sensors.len() - 1]
@@ -201,13 +223,13 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
                     .collect::<Vec<_>>()
                 })
         })
-        .filter(|pos| pos.0 >= 0 && pos.0 <= SQUARE_SIZE && pos.1 >= 0 && pos.1 <= SQUARE_SIZE)
+        .filter(|pos| pos.0 >= 0 && pos.0 <= square_size && pos.1 >= 0 && pos.1 <= square_size)
         .chain([
             // edges are special cause they can be constrict by a single sensor
             (0, 0),
-            (0, SQUARE_SIZE),
-            (SQUARE_SIZE, 0),
-            (SQUARE_SIZE, SQUARE_SIZE),
+            (0, square_size),
+            (square_size, 0),
+            (square_size, square_size),
         ])
         // now we check that the points are not inside any other sensor (so it's a valid points)
         .filter(|pts| {
@@ -225,8 +247,8 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 /*
 
 /// Solution with iterator over beacon borders
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let sensors: Vec<_> = find_radii(parse_input(input)).collect();
+fn part2((reports, _): &(Vec<Report>, PuzzleParams)) -> Result<PuzzleResult, Box<dyn Error>> {
+    let sensors: Vec<_> = find_radii(reports.iter().copied()).collect();
     const SQUARE_SIZE: isize = 4000000;
 
     // there must be a point that's papable on the border of the iterators
@@ -327,8 +349,8 @@ impl Iterator for PointsOn {
 }
 
 /// Solution with gradient descend
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let sensors: Vec<_> = find_radii(parse_input(input)).collect();
+fn part2((reports, _): &(Vec<Report>, PuzzleParams)) -> Result<PuzzleResult, Box<dyn Error>> {
+    let sensors: Vec<_> = find_radii(reports.iter().copied()).collect();
     // calculate a score that's 0 only on the possible places
     // it's made up of piramids with the point on the sensor, and flatting out over the beacon radius
     let score = |pos: (isize, isize)| -> usize {
@@ -387,3 +409,29 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 }
 
 */
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "line_y",
+            default: "2000000",
+            description: "Row where the first part counts the impossible positions",
+        },
+        Param {
+            name: "square_size",
+            default: "4000000",
+            description: "Side of the square where the distress beacon is searched",
+        },
+    ];
+
+    type Input<'a> = (Vec<Report>, PuzzleParams);
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok((parse_input(input)?, PuzzleParams::new(params)?))
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
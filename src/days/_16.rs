@@ -2,12 +2,18 @@ use std::{collections::HashMap, error::Error};
 
 use regex::Regex;
 
-use super::PuzzleResult;
+use super::{
+    parse_at, search::floyd_warshall, Day, Param, Params, ParseError, PartFn, PuzzleResult,
+    Solution,
+};
 
-fn parse_input<'a>(input: &'a str) -> (usize, Vec<usize>, Vec<Vec<usize>>) {
+/// Starting valve, flows and distances between the valves
+type Cave = (usize, Vec<usize>, Vec<Vec<usize>>);
+
+fn parse_input<'a>(input: &'a str) -> Result<Cave, ParseError> {
     lazy_static! {
         static ref LINE_RE: Regex = Regex::new(
-            r"(?m)^Valve (..) has flow rate=(\d+); tunnels? leads? to valves? (..(?:, ..)*)$"
+            r"^Valve (..) has flow rate=(\d+); tunnels? leads? to valves? (..(?:, ..)*)$"
         )
         .unwrap();
     }
@@ -29,50 +35,73 @@ fn parse_input<'a>(input: &'a str) -> (usize, Vec<usize>, Vec<Vec<usize>>) {
     };
 
     let mut nodes: Vec<(_, Vec<_>)> = vec![];
-    for captures in LINE_RE.captures_iter(input) {
-        let id = (&mut name_id)(captures.get(1).unwrap().as_str());
+    let mut described = vec![];
+    let mut links = vec![];
+    for line in input.trim().lines() {
+        let line = line.trim();
+        let captures = LINE_RE
+            .captures(line)
+            .ok_or_else(|| ParseError::at(line, "a valve with its flow rate and tunnels"))?;
+        let name = captures.get(1).unwrap().as_str();
+        let id = (&mut name_id)(name);
         let node = (
-            captures[2].parse().unwrap(),
+            parse_at(captures.get(2).unwrap().as_str(), "a flow rate")?,
             captures
                 .get(3)
                 .unwrap()
                 .as_str()
                 .split(", ")
+                .inspect(|&link| links.push(link))
                 .map(&mut name_id)
                 .collect(),
         );
         if nodes.len() <= id {
-            nodes.resize(id + 1, (0, vec![]))
+            nodes.resize(id + 1, (0, vec![]));
+            described.resize(id + 1, false)
+        }
+        if described[id] {
+            return Err(ParseError::at(name, "a valve not described before"));
         }
         nodes[id] = node;
+        described[id] = true
+    }
+    // the tunnels must lead to known valves
+    let is_described = |id: usize| described.get(id) == Some(&true);
+    for link in links {
+        if !is_described(name_id(link)) {
+            return Err(ParseError::at(link, "a described valve"));
+        }
+    }
+    let start = name_id("AA");
+    if !is_described(start) {
+        return Err(ParseError::at(
+            &input[input.len()..],
+            "a description of valve AA",
+        ));
     }
 
     // now we calculate the distance between each node
-    let mut distances: Vec<Vec<_>> = vec![vec![usize::MAX / 2; nodes.len()]; nodes.len()];
+    let mut distances: Vec<Vec<_>> = vec![vec![None; nodes.len()]; nodes.len()];
     // adding the trivial distances
     for (node, (_, links)) in nodes.iter().enumerate() {
-        distances[node][node] = 0;
         for other_node in links {
-            distances[node][*other_node] = 1;
-        }
-    }
-    // Floyd–Warshall algorithm
-    for k in 0..nodes.len() {
-        // todo: halve these cycles
-        for i in 0..nodes.len() {
-            for j in 0..nodes.len() {
-                let dist_by_k = distances[i][k] + distances[k][j];
-                if distances[i][j] > dist_by_k {
-                    distances[i][j] = dist_by_k;
-                }
-            }
+            distances[node][*other_node] = Some(1);
         }
     }
+    floyd_warshall(&mut distances);
+    // unreachable valves are too far to be opened in time
+    let distances = distances
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|d| d.unwrap_or(usize::MAX / 2))
+                .collect()
+        })
+        .collect();
 
     let flows = nodes.iter().map(|(flow, _)| *flow).collect();
 
-    // finding the possible
-    (name_id("AA"), flows, distances)
+    Ok((start, flows, distances))
 }
 
 #[cfg(feature = "_16_bitmaps")]
@@ -171,13 +200,32 @@ fn paths(
     complete_paths
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let (pos, flows, distances) = parse_input(input);
+/// Starting valve, flows and distances between the valves, and parameters
+type Scan = (usize, Vec<usize>, Vec<Vec<usize>>, PuzzleParams);
 
+/// Parameters of the puzzle
+struct PuzzleParams {
+    /// Minutes before the eruption
+    minutes: usize,
+    /// Minutes left after teaching the elephant
+    minutes_with_elephant: usize,
+}
+impl PuzzleParams {
+    fn new(params: &Params) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            minutes: params.get("minutes")?,
+            minutes_with_elephant: params.get("minutes_with_elephant")?,
+        })
+    }
+}
+
+fn part1(
+    &(pos, ref flows, ref distances, PuzzleParams { minutes, .. }): &Scan,
+) -> Result<PuzzleResult, Box<dyn Error>> {
     let flows: Vec<(Valves, usize)> = paths(
         pos,
-        30,
-        &flows,
+        minutes,
+        flows,
         &distances.iter().map(|l| l.as_slice()).collect::<Vec<_>>(),
     );
 
@@ -189,13 +237,21 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     ))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let (pos, flows, distances) = parse_input(input);
-
+fn part2(
+    &(
+        pos,
+        ref flows,
+        ref distances,
+        PuzzleParams {
+            minutes_with_elephant,
+            ..
+        },
+    ): &Scan,
+) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut flows: Vec<(Valves, usize)> = paths(
         pos,
-        26,
-        &flows,
+        minutes_with_elephant,
+        flows,
         &distances.iter().map(|l| l.as_slice()).collect::<Vec<_>>(),
     );
 
@@ -223,3 +279,30 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     // find the best flow
     Ok(PuzzleResult::Numeric((max_flow) as _))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "minutes",
+            default: "30",
+            description: "Minutes before the eruption",
+        },
+        Param {
+            name: "minutes_with_elephant",
+            default: "26",
+            description: "Minutes left after teaching the elephant",
+        },
+    ];
+
+    type Input<'a> = Scan;
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        let (pos, flows, distances) = parse_input(input)?;
+        Ok((pos, flows, distances, PuzzleParams::new(params)?))
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
@@ -1,6 +1,6 @@
 use std::error::Error;
 
-use super::PuzzleResult;
+use super::{Day, Param, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 const ROCKS: &[&[&[bool]]] = &[
     &[&[true, true, true, true]],
@@ -26,10 +26,11 @@ enum PushDirection {
 
 fn parse_input(input: &str) -> Result<Box<[PushDirection]>, Box<dyn Error>> {
     use PushDirection::*;
-    Result::<Vec<_>, _>::from_iter(input.trim().chars().map(|ch| match ch {
+    let input = input.trim();
+    Result::<Vec<_>, _>::from_iter(input.char_indices().map(|(idx, ch)| match ch {
         '<' => Ok(LEFT),
         '>' => Ok(RIGHT),
-        ch => Err(format!("Unrecognized char {ch}")),
+        ch => Err(ParseError::at(&input[idx..idx + ch.len_utf8()], "< or >")),
     }))
     .map(|vec| vec.into_boxed_slice())
     .map_err(|err| err.into())
@@ -47,15 +48,32 @@ fn collide(rock: &[&[bool]], rock_left: usize, rock_bottom: usize, lines: &[[boo
     false
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let pushes = parse_input(input)?;
+/// Parameters of the puzzle
+struct PuzzleParams {
+    /// Rocks to drop in the first part
+    rocks: usize,
+    /// Rocks to drop in the second part
+    many_rocks: usize,
+}
+impl PuzzleParams {
+    fn new(params: &Params) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            rocks: params.get("rocks")?,
+            many_rocks: params.get("many_rocks")?,
+        })
+    }
+}
+
+fn part1(
+    &(ref pushes, PuzzleParams { rocks, .. }): &(Box<[PushDirection]>, PuzzleParams),
+) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut pushes_iter = pushes.iter().cycle();
 
     let mut lines: Vec<[bool; 7]> = vec![];
     let mut first_empty_line = 0;
 
     // dropping rocks
-    for &rock in ROCKS.iter().cycle().take(2022) {
+    for &rock in ROCKS.iter().cycle().take(rocks) {
         let mut rock_bottom = first_empty_line + 3;
         let mut rock_left = 2;
         let rock_height = rock.len();
@@ -142,8 +160,9 @@ fn print_state(rock: &[&[bool]], rock_left: usize, rock_bottom: usize, lines: &[
     println!();
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let pushes = parse_input(input)?;
+fn part2(
+    &(ref pushes, PuzzleParams { many_rocks, .. }): &(Box<[PushDirection]>, PuzzleParams),
+) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut pushes_iter = pushes.iter().enumerate().cycle().peekable();
 
     let mut lines: Vec<[bool; 7]> = vec![];
@@ -158,7 +177,7 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 
     // dropping rocks
     for (rock_total, (rock_num, &rock)) in ROCKS.iter().enumerate().cycle().enumerate() {
-        if rock_total + additional_rocks == 1000000000000 {
+        if rock_total + additional_rocks == many_rocks {
             break;
         }
         let mut rock_bottom = first_empty_line + 3;
@@ -181,7 +200,7 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
                     let rep_height: usize =
                         log[log.len() - rep_len..].iter().map(|(_, dh)| *dh).sum();
                     // calculate number of repetitions
-                    let repeats = (1000000000000 - rock_total) / rep_rocks;
+                    let repeats = (many_rocks - rock_total) / rep_rocks;
 
                     additional_rocks = repeats * rep_rocks;
                     additional_height = repeats * rep_height;
@@ -248,3 +267,29 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         (first_empty_line + additional_height) as _,
     ))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Pyroclastic Flow";
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rocks",
+            default: "2022",
+            description: "Rocks to drop in the first part",
+        },
+        Param {
+            name: "many_rocks",
+            default: "1000000000000",
+            description: "Rocks to drop in the second part",
+        },
+    ];
+
+    type Input<'a> = (Box<[PushDirection]>, PuzzleParams);
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok((parse_input(input)?, PuzzleParams::new(params)?))
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
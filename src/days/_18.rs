@@ -1,21 +1,25 @@
-use std::{borrow::Borrow, error::Error, num::ParseIntError};
+use std::{borrow::Borrow, error::Error};
 
-use super::PuzzleResult;
+use super::{
+    parse_at,
+    search::{Graph, Search},
+    Day, Params, ParseError, PartFn, PuzzleResult, Solution,
+};
 
 fn parse_input(input: &str) -> Result<Vec<(isize, isize, isize)>, Box<dyn Error>> {
     Result::from_iter(input.trim().lines().map(|line| {
-        Result::<Vec<isize>, Box<dyn Error>>::from_iter(
-            line.split(',')
-                .map(|v| v.trim().parse().map_err(|err: ParseIntError| err.into())),
+        Result::<Vec<isize>, ParseError>::from_iter(
+            line.split(',').map(|v| parse_at(v.trim(), "a coordinate")),
         )
         .and_then(|values| {
             if values.len() == 3 {
                 Ok((values[0], values[1], values[2]))
             } else {
-                Err(format!("Expected 3 values on a line, not {}", values.len()).into())
+                Err(ParseError::at(line, "3 coordinates separated by commas"))
             }
         })
     }))
+    .map_err(|err| err.into())
 }
 
 #[inline]
@@ -31,12 +35,11 @@ fn neighbours((x, y, z): (isize, isize, isize)) -> impl Iterator<Item = (isize,
     .into_iter()
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let rock_cubes: Vec<(isize, isize, isize)> = parse_input(input)?;
-    let (bb, cubes) = make_grid(&rock_cubes);
+fn part1(rock_cubes: &[(isize, isize, isize)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let (bb, cubes) = make_grid(rock_cubes);
 
     let mut faces = 0;
-    for cube in rock_cubes {
+    for &cube in rock_cubes {
         // adding the free faces
         faces += 6 - neighbours(cube)
             .filter(|neighbour| cubes[bb.idx(*neighbour)])
@@ -45,36 +48,54 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     Ok(PuzzleResult::Numeric(faces as _))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let cubes: Vec<(isize, isize, isize)> = parse_input(input)?;
-    let (bb, cubes) = make_grid(&cubes);
+/// The air of the bounding box, where the steam can flow
+struct Air<'a> {
+    bb: &'a BoundingBox,
+    cubes: &'a [bool],
+}
+impl Graph for Air<'_> {
+    type Node = (isize, isize, isize);
+    type Cost = usize;
 
-    let mut examined = bb.grid(false);
-    let mut to_examine = Vec::with_capacity(bb.size());
-    to_examine.push((bb.xmin, bb.ymin, bb.zmin));
-    debug_assert!(!cubes[bb.idx(*to_examine.last().unwrap())]);
+    fn size(&self) -> usize {
+        self.bb.size()
+    }
+    fn index(&self, &cube: &(isize, isize, isize)) -> usize {
+        self.bb.idx(cube)
+    }
+    fn neighbours(
+        &self,
+        &cube: &(isize, isize, isize),
+        mut edge: impl FnMut((isize, isize, isize), usize),
+    ) {
+        for neighbour in neighbours(cube) {
+            // do not examine outside the bounding box, or into the rock
+            if self.bb.contains(neighbour) && !self.cubes[self.bb.idx(neighbour)] {
+                edge(neighbour, 1)
+            }
+        }
+    }
+}
 
-    let mut faces = 0;
+fn part2(rock_cubes: &[(isize, isize, isize)]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let (bb, cubes) = make_grid(rock_cubes);
+    let air = Air {
+        bb: &bb,
+        cubes: &cubes,
+    };
 
     // flood
-    while let Some(cube) = to_examine.pop() {
-        if !examined[bb.idx(cube)] {
-            examined[bb.idx(cube)] = true;
-            for neighbour in neighbours(cube) {
-                if !(bb.contains(neighbour)) {
-                    continue; // do not examine outside the bounding box
-                }
-                if examined[bb.idx(neighbour)] {
-                    continue; // do not search twice
-                }
-                if cubes[bb.idx(neighbour)] {
-                    faces += 1; // count the face between the flood and the rock
-                    continue;
-                }
-                // a new cube
-                to_examine.push(neighbour)
-            }
-        }
+    let start = (bb.xmin, bb.ymin, bb.zmin);
+    debug_assert!(!cubes[bb.idx(start)]);
+    let mut flood = Search::new(&air);
+    flood.bfs([start], |_| false);
+
+    // count the faces between the flood and the rock
+    let mut faces = 0;
+    for &cube in rock_cubes {
+        faces += neighbours(cube)
+            .filter(|neighbour| flood.cost(neighbour).is_some())
+            .count()
     }
 
     Ok(PuzzleResult::Numeric(faces as _))
@@ -163,3 +184,17 @@ fn make_grid(cubes: &[(isize, isize, isize)]) -> (BoundingBox, Vec<bool>) {
     }
     (bb, grid)
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 18;
+    const TITLE: &'static str = "Boiling Boulders";
+
+    type Input<'a> = Vec<(isize, isize, isize)>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -8,7 +8,7 @@ use std::fmt::Display;
 
 use regex::Regex;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
 #[derive(Debug)]
 struct Blueprint {
@@ -23,26 +23,26 @@ struct Blueprint {
 fn parse_input(input: &str) -> Result<Vec<Blueprint>, Box<dyn Error>> {
     lazy_static! {
         static ref BLUEPRINT_RE: Regex = Regex::new(
-            r"(?m)^Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.$"
+            r"^Blueprint (\d+): Each ore robot costs (\d+) ore. Each clay robot costs (\d+) ore. Each obsidian robot costs (\d+) ore and (\d+) clay. Each geode robot costs (\d+) ore and (\d+) obsidian.$"
         ).unwrap();
     }
     let mut blueprints = vec![];
-    for (i, captures) in BLUEPRINT_RE.captures_iter(input).enumerate() {
-        if i + 1 != captures[1].parse()? {
-            return Err(format!(
-                "Mismatched blueprint order: blueprint {} is in place {}",
-                &captures[1],
-                i + 1
-            )
-            .into());
+    for (i, line) in input.trim().lines().map(str::trim).enumerate() {
+        let captures = BLUEPRINT_RE
+            .captures(line)
+            .ok_or_else(|| ParseError::at(line, "a blueprint"))?;
+        let cost = |n: usize| parse_at(captures.get(n).unwrap().as_str(), "a cost");
+        let number = captures.get(1).unwrap().as_str();
+        if i + 1 != parse_at(number, "a blueprint number")? {
+            return Err(ParseError::at(number, format!("blueprint {}", i + 1)).into());
         }
         blueprints.push(Blueprint {
-            ore_robot_cost_ore: captures[2].parse()?,
-            clay_robot_cost_ore: captures[3].parse()?,
-            obsidian_robot_cost_ore: captures[4].parse()?,
-            obsidian_robot_cost_clay: captures[5].parse()?,
-            geode_robot_cost_ore: captures[6].parse()?,
-            geode_robot_cost_obsidian: captures[7].parse()?,
+            ore_robot_cost_ore: cost(2)?,
+            clay_robot_cost_ore: cost(3)?,
+            obsidian_robot_cost_ore: cost(4)?,
+            obsidian_robot_cost_clay: cost(5)?,
+            geode_robot_cost_ore: cost(6)?,
+            geode_robot_cost_obsidian: cost(7)?,
         })
     }
     Ok(blueprints)
@@ -380,7 +380,7 @@ fn max_geodes(minutes: usize, blueprint: &Blueprint) -> (usize, Log) {
                     .saturating_sub(obsidian)
                     .div_ceil(obsidian_bots),
             );
-            debug_assert!(time_to_wait < minutes_left - 1);
+            debug_assert!(time_to_wait < minutes_left);
 
             branches.push((
                 minutes_left - time_to_wait,
@@ -422,7 +422,7 @@ fn max_geodes(minutes: usize, blueprint: &Blueprint) -> (usize, Log) {
                     .saturating_sub(clay)
                     .div_ceil(clay_bots),
             );
-            debug_assert!(time_to_wait < minutes_left - 1);
+            debug_assert!(time_to_wait < minutes_left);
 
             branches.push((
                 minutes_left - time_to_wait,
@@ -457,7 +457,7 @@ fn max_geodes(minutes: usize, blueprint: &Blueprint) -> (usize, Log) {
                 .clay_robot_cost_ore
                 .saturating_sub(ore)
                 .div_ceil(ore_bots);
-            debug_assert!(time_to_wait < minutes_left - 1);
+            debug_assert!(time_to_wait < minutes_left);
 
             branches.push((
                 minutes_left - time_to_wait,
@@ -492,7 +492,7 @@ fn max_geodes(minutes: usize, blueprint: &Blueprint) -> (usize, Log) {
                 .ore_robot_cost_ore
                 .saturating_sub(ore)
                 .div_ceil(ore_bots);
-            debug_assert!(time_to_wait < minutes_left - 1);
+            debug_assert!(time_to_wait < minutes_left);
 
             branches.push((
                 minutes_left - time_to_wait,
@@ -530,8 +530,7 @@ fn max_geodes(minutes: usize, blueprint: &Blueprint) -> (usize, Log) {
     (best_result, best_trace)
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let blueprints = parse_input(input)?;
+fn part1(blueprints: &[Blueprint]) -> Result<PuzzleResult, Box<dyn Error>> {
     let blueprints_geodes = blueprints.iter().enumerate().map(|(_i, blueprint)| {
         let (geodes, _trace) = max_geodes(24, blueprint);
         #[cfg(feature="_19_trace")]{
@@ -551,9 +550,9 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 }
 
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let all_blueprints = parse_input(input)?;
-    let blueprints = all_blueprints[..3].iter();
+fn part2(all_blueprints: &[Blueprint]) -> Result<PuzzleResult, Box<dyn Error>> {
+    // the elephants ate all but the first three blueprints (if there were that many)
+    let blueprints = all_blueprints.iter().take(3);
     let blueprints_geodes = blueprints.enumerate().map(|(_i, blueprint)| {
         let (geodes, _trace) = max_geodes(32, blueprint);
         #[cfg(feature="_19_trace")]{
@@ -571,3 +570,16 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     ))
 }
 
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 19;
+    const TITLE: &'static str = "Not Enough Minerals";
+
+    type Input<'a> = Vec<Blueprint>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -1,14 +1,23 @@
-use std::{error::Error, num::ParseIntError};
+use std::error::Error;
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
-fn parse_input(input: &str) -> Result<Box<[isize]>, ParseIntError> {
-    Result::from_iter(input.trim().lines().map(|line| line.trim().parse()))
+fn parse_input(input: &str) -> Result<Box<[isize]>, ParseError> {
+    let numbers: Box<[isize]> = Result::from_iter(
+        input
+            .trim()
+            .lines()
+            .map(|line| parse_at(line.trim(), "a number")),
+    )?;
+    // mixing moves the numbers around the others
+    if numbers.len() < 2 {
+        return Err(ParseError::at(input.trim(), "at least two numbers"));
+    }
+    Ok(numbers)
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input = parse_input(input)?;
-    let mut list: Box<[_]> = input.into_iter().map(|v| (*v, true)).collect();
+fn part1(input: &[isize]) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut list: Box<[_]> = input.iter().map(|v| (*v, true)).collect();
 
     // decrypt
     for i in 0..list.len() {
@@ -40,10 +49,9 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     ))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let input = parse_input(input)?;
+fn part2(input: &[isize]) -> Result<PuzzleResult, Box<dyn Error>> {
     let mut list: Vec<_> = input
-        .into_iter()
+        .iter()
         .enumerate()
         .map(|v| (v.0, v.1 * 811589153))
         .collect();
@@ -78,3 +86,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         (list[p1].1 + list[p2].1 + list[p3].1) as _,
     ))
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 20;
+    const TITLE: &'static str = "Grove Positioning System";
+
+    type Input<'a> = Box<[isize]>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(parse_input(input)?)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input), |input| part2(input)];
+}
@@ -6,9 +6,9 @@ use std::{
     rc::Rc,
 };
 
-use super::PuzzleResult;
+use super::{parse_at, Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum MonkeIntruction<'inp> {
     Const(isize),
     Sum(&'inp str, &'inp str),
@@ -139,7 +139,7 @@ fn monke_name(name: &str) -> Result<&str, Box<dyn Error>> {
     if name.len() == 4 && name.chars().all(|ch| ch.is_alphabetic()) {
         Ok(name)
     } else {
-        Err(format!("{name} is not a monke name").into())
+        Err(ParseError::at(name, "a monke name").into())
     }
 }
 
@@ -156,7 +156,7 @@ fn parse_name_and_op<'a, 'b>(
     } else if let Some((a, b)) = op.split_once('/') {
         MonkeIntruction::Div(monke_name(a)?, monke_name(b)?)
     } else {
-        MonkeIntruction::Const(op.trim().parse()?)
+        MonkeIntruction::Const(parse_at(op.trim(), "a number or an operation")?)
     };
     Ok((name, monke))
 }
@@ -164,7 +164,7 @@ fn parse_name_and_op<'a, 'b>(
 fn parse_input(input: &str) -> Result<HashMap<&str, MonkeIntruction>, Box<dyn Error>> {
     Result::from_iter(input.trim().lines().map(|line| {
         line.split_once(':')
-            .ok_or("Missing :".into())
+            .ok_or_else(|| ParseError::at(line, "a monke name and its job").into())
             .and_then(parse_name_and_op)
     }))
 }
@@ -560,15 +560,16 @@ impl Div<isize> for MonkeFract {
     }
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let monkeys = parse_input(input)?;
-
-    Ok(PuzzleResult::Numeric((monkeys["root"].yell(&monkeys)) as _))
+fn part1<'a>(
+    monkeys: &HashMap<&'a str, MonkeIntruction<'a>>,
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    Ok(PuzzleResult::Numeric((monkeys["root"].yell(monkeys)) as _))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let monkeys = parse_input(input)?;
-    let eq = MonkeFract::from(root_eq(monkeys)?.const_propagation());
+fn part2<'a>(
+    monkeys: &HashMap<&'a str, MonkeIntruction<'a>>,
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let eq = MonkeFract::from(root_eq(monkeys.clone())?.const_propagation());
 
     // print!("{eq}");
 
@@ -592,3 +593,17 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
         _ => unimplemented!("Solutions of other degrees are still unimplemented"),
     }
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 21;
+    const TITLE: &'static str = "Monkey Math";
+
+    type Input<'a> = HashMap<&'a str, MonkeIntruction<'a>>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
@@ -1,12 +1,16 @@
 use std::{
     error::Error,
+    fs,
     io::{stdin, stdout},
+    path::Path,
 };
 
-use grid::Grid;
+use phf_macros::phf_map;
 use termion::{input::TermRead, raw::IntoRawMode};
 
-use super::PuzzleResult;
+use super::{
+    grid::Grid, parse_at, Day, Params, ParseError, PartFn, PuzzleResult, ReprFn, Solution,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Turn {
@@ -137,65 +141,65 @@ impl WarpEnters {
     }
 }
 
-fn parse_input(
-    input: &str,
-) -> Result<((Pos, Grid<Tile>), (Box<[usize]>, Box<[Turn]>)), Box<dyn Error>> {
+/// Starting position and map, and the path to follow
+type Notes = ((Pos, Grid<Tile>), (Box<[usize]>, Box<[Turn]>));
+
+fn parse_input(input: &str) -> Result<Notes, Box<dyn Error>> {
     let (map, path) = input
         .split_once("\n\n")
-        .ok_or(<Box<dyn Error>>::from("Missing empty separator line"))?;
+        .ok_or_else(|| ParseError::at(&input[input.len()..], "an empty line after the map"))?;
 
     Ok((parse_map(map)?, parse_path(path)?))
 }
 
 fn parse_path(path: &str) -> Result<(Box<[usize]>, Box<[Turn]>), Box<dyn Error>> {
-    let segments: Box<[usize]> =
-        Result::from_iter(path.trim().split(&['R', 'L']).map(|s| s.parse()))?;
-    let turns: Box<[Turn]> = path
-        .trim()
-        .chars()
-        .filter_map(|ch| match ch {
-            'R' => Some(Turn::RIGHT),
-            'L' => Some(Turn::LEFT),
-            _ => None,
-        })
-        .collect();
-    debug_assert!(segments.len() == turns.len() + 1);
-    Ok((segments, turns))
+    let mut segments = vec![];
+    let mut turns = vec![];
+    // alternating segments and turns, starting and ending with a segment
+    let mut rest = path.trim();
+    loop {
+        let digits = rest
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(rest.len());
+        segments.push(parse_at(&rest[..digits], "a number of steps")?);
+        rest = &rest[digits..];
+        let Some(ch) = rest.chars().next() else {
+            break;
+        };
+        turns.push(match ch {
+            'R' => Turn::RIGHT,
+            'L' => Turn::LEFT,
+            _ => return Err(ParseError::at(&rest[..ch.len_utf8()], "R or L").into()),
+        });
+        rest = &rest[1..];
+    }
+    Ok((segments.into_boxed_slice(), turns.into_boxed_slice()))
 }
 
 fn parse_map(map: &str) -> Result<(Pos, Grid<Tile>), Box<dyn Error>> {
-    let height = map.lines().count() + 2;
-    let width = map.lines().map(|l| l.len()).max().unwrap_or(0) + 2;
-
-    let mut grid = Grid::new(height, width);
-
-    let cells = map.lines().enumerate().flat_map(|(r, line)| {
-        line.chars().enumerate().map(move |(c, ch)| {
-            match ch {
-                ' ' => Ok(Tile::Extern),
-                '#' => Ok(Tile::Wall),
-                '.' => Ok(Tile::Space),
-                ch => Err(Box::<dyn Error>::from(format!("Unrecognized char {ch}"))),
-            }
-            // adding coordinates, with the padding
-            .map(|t| ((r + 1, c + 1), t))
-        })
-    });
-
     let mut start = None;
 
-    for cell in cells {
-        let ((row, column), tile) = cell?;
+    let grid = Grid::parse(map, Tile::Extern, "' ', '#' or '.'", |(col, row), ch| {
+        let tile = match ch {
+            ' ' => Tile::Extern,
+            '#' => Tile::Wall,
+            '.' => Tile::Space,
+            _ => return None,
+        };
         if tile == Tile::Space && start.is_none() {
-            start = Some((row, column))
+            start = Some((row, col))
         }
-        grid[row][column] = tile;
-    }
-
-    let start = start.ok_or_else(|| Box::<dyn Error>::from("Grid is empty!"))?;
+        Some(tile)
+    })?
+    // adding the padding
+    .expand(1, Tile::Extern);
+
+    let start = start.ok_or_else(|| {
+        ParseError::at(map.lines().next().unwrap_or(map), "a map with an open tile")
+    })?;
     let pos = Pos {
-        row: start.0,
-        col: start.1,
+        row: start.0 + 1,
+        col: start.1 + 1,
         direction: Direction::RIGHT,
     };
 
@@ -209,7 +213,7 @@ fn advance(mut pos: Pos, len: usize, field: &Grid<Tile>) -> Pos {
             col: new_col,
             direction,
         } = pos.stepped();
-        match field.get(new_row, new_col) {
+        match field.get((new_col, new_row)) {
             Some(Tile::Space) => {
                 pos = pos.stepped(); // no obstacles
             }
@@ -237,22 +241,22 @@ fn advance(mut pos: Pos, len: usize, field: &Grid<Tile>) -> Pos {
 
 fn put_wrapping_warps(mut field: Grid<Tile>) -> Grid<Tile> {
     // orizontal warps
-    for row in 0..field.rows() {
+    for row in 0..field.height() {
         // warps from right side to left side
 
         let mut col = 0;
         // go to the field
-        while let Some(Tile::Extern | Tile::Warp(_)) = field.get(row, col) {
+        while let Some(Tile::Extern | Tile::Warp(_)) = field.get((col, row)) {
             col += 1;
         }
         let warp_dest =
-            (field.get(row, col) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
+            (field.get((col, row)) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
         // skip the field
-        while let Some(Tile::Space | Tile::Wall) = field.get(row, col) {
+        while let Some(Tile::Space | Tile::Wall) = field.get((col, row)) {
             col += 1;
         }
         // check it was not an empty row
-        if let Some(tile) = field.get_mut(row, col) {
+        if let Some(tile) = field.get_mut((col, row)) {
             *tile = match tile {
                 Tile::Space | Tile::Wall => unreachable!(),
                 Tile::Extern => Tile::Warp(Box::new(WarpEnters {
@@ -268,19 +272,19 @@ fn put_wrapping_warps(mut field: Grid<Tile>) -> Grid<Tile> {
 
         // warps from left side to right side
 
-        let mut col = field.cols() - 1;
+        let mut col = field.width() - 1;
         // go to the field
-        while let Some(Tile::Extern | Tile::Warp(_)) = field.get(row, col) {
-            col -= 1;
+        while let Some(Tile::Extern | Tile::Warp(_)) = field.get((col, row)) {
+            col = col.wrapping_sub(1); // going out of the grid on an empty row
         }
         let warp_dest =
-            (field.get(row, col) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
+            (field.get((col, row)) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
         // skip the field
-        while let Some(Tile::Space | Tile::Wall) = field.get(row, col) {
+        while let Some(Tile::Space | Tile::Wall) = field.get((col, row)) {
             col -= 1;
         }
         // check it was not an empty row
-        if let Some(tile) = field.get_mut(row, col) {
+        if let Some(tile) = field.get_mut((col, row)) {
             *tile = match tile {
                 Tile::Space | Tile::Wall => unreachable!(),
                 Tile::Extern => Tile::Warp(Box::new(WarpEnters {
@@ -296,22 +300,22 @@ fn put_wrapping_warps(mut field: Grid<Tile>) -> Grid<Tile> {
     }
 
     // vertical warps
-    for col in 0..field.cols() {
+    for col in 0..field.width() {
         // warps from bottom side to up side
 
         let mut row = 0;
         // go to the field
-        while let Some(Tile::Extern | Tile::Warp(_)) = field.get(row, col) {
+        while let Some(Tile::Extern | Tile::Warp(_)) = field.get((col, row)) {
             row += 1;
         }
         let warp_dest =
-            (field.get(row, col) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
+            (field.get((col, row)) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
         // skip the field
-        while let Some(Tile::Space | Tile::Wall) = field.get(row, col) {
+        while let Some(Tile::Space | Tile::Wall) = field.get((col, row)) {
             row += 1;
         }
         // check it was not an empty col
-        if let Some(tile) = field.get_mut(row, col) {
+        if let Some(tile) = field.get_mut((col, row)) {
             *tile = match tile {
                 Tile::Space | Tile::Wall => unreachable!(),
                 Tile::Extern => Tile::Warp(Box::new(WarpEnters {
@@ -327,19 +331,19 @@ fn put_wrapping_warps(mut field: Grid<Tile>) -> Grid<Tile> {
 
         // warps from bottom side to up side
 
-        let mut row = field.rows() - 1;
+        let mut row = field.height() - 1;
         // go to the field
-        while let Some(Tile::Extern | Tile::Warp(_)) = field.get(row, col) {
-            row -= 1;
+        while let Some(Tile::Extern | Tile::Warp(_)) = field.get((col, row)) {
+            row = row.wrapping_sub(1); // going out of the grid on an empty col
         }
         let warp_dest =
-            (field.get(row, col) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
+            (field.get((col, row)) == Some(&Tile::Space)).then(|| (row, col, Turn::Nothing));
         // skip the field
-        while let Some(Tile::Space | Tile::Wall) = field.get(row, col) {
+        while let Some(Tile::Space | Tile::Wall) = field.get((col, row)) {
             row -= 1;
         }
         // check it was not an empty col
-        if let Some(tile) = field.get_mut(row, col) {
+        if let Some(tile) = field.get_mut((col, row)) {
             *tile = match tile {
                 Tile::Space | Tile::Wall => unreachable!(),
                 Tile::Extern => Tile::Warp(Box::new(WarpEnters {
@@ -382,18 +386,18 @@ fn put_warp_line(
         line(positions.0, positions.1, len).zip(line(dests.0, dests.1, len))
     {
         /*
-        if field.get(warp_dest.0, warp_dest.1).is_none()
-            || field.get(warp_pos.0, warp_pos.1).is_none()
+        if field.get((warp_dest.1, warp_dest.0)).is_none()
+            || field.get((warp_pos.1, warp_pos.0)).is_none()
         {
             return;
         }*/
         let warp_dest = (field
-            .get(warp_dest.0, warp_dest.1)
+            .get((warp_dest.1, warp_dest.0))
             .expect("Destinations should be inside the field")
             == &Tile::Space)
             .then(|| (warp_dest.0, warp_dest.1, turn));
         let warp_tile = field
-            .get_mut(warp_pos.0, warp_pos.1)
+            .get_mut((warp_pos.1, warp_pos.0))
             .expect("Positions should be inside the field");
         use Direction::*;
         *warp_tile = match (&warp_tile, enter_direction) {
@@ -463,7 +467,7 @@ fn put_coupled_warp_lines(
 
 fn put_cube_warps(mut field: Grid<Tile>) -> Grid<Tile> {
     assert!(
-        field.size() == (200 + 2, 150 + 2),
+        field.shape() == (150 + 2, 200 + 2),
         "Different field sizes are unimplemented"
     );
 
@@ -567,9 +571,10 @@ fn put_cube_warps(mut field: Grid<Tile>) -> Grid<Tile> {
     field
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let ((mut pos, field), (segments, turns)) = parse_input(input)?;
-    let field = put_wrapping_warps(field);
+fn part1(
+    &((mut pos, ref field), (ref segments, ref turns)): &Notes,
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let field = put_wrapping_warps(field.clone());
 
     for (segment, turn) in segments.iter().zip(turns.iter()) {
         pos = advance(pos, *segment, &field);
@@ -582,9 +587,10 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     ))
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let ((mut pos, field), (segments, turns)) = parse_input(input)?;
-    let field = put_cube_warps(field);
+fn part2(
+    &((mut pos, ref field), (ref segments, ref turns)): &Notes,
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let field = put_cube_warps(field.clone());
 
     //guided(pos, &field)?;
 
@@ -603,6 +609,46 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
     ))
 }
 
+/// Draw the walk of the first part on the map
+fn visualize_walk(input: &str, out: &Path) -> Result<(), Box<dyn Error>> {
+    let ((mut pos, field), (segments, turns)) = parse_input(input)?;
+    let field = put_wrapping_warps(field);
+
+    // last direction the walker had on each tile
+    let mut trail = Grid::new_like(&field, None);
+    let mut walk = |mut pos: Pos, len: usize| {
+        trail[(pos.col, pos.row)] = Some(pos.direction);
+        for _ in 0..len {
+            pos = advance(pos, 1, &field);
+            trail[(pos.col, pos.row)] = Some(pos.direction);
+        }
+        pos
+    };
+    for (segment, turn) in segments.iter().zip(turns.iter()) {
+        pos = walk(pos, *segment).turned(*turn);
+    }
+    walk(pos, *segments.last().unwrap());
+
+    let mut drawing = String::with_capacity(field.height() * (field.width() + 1));
+    for row in 0..field.height() {
+        for (col, tile) in field.row(row).iter().enumerate() {
+            drawing.push(match (trail[(col, row)], tile) {
+                (Some(Direction::RIGHT), _) => '>',
+                (Some(Direction::DOWN), _) => 'v',
+                (Some(Direction::LEFT), _) => '<',
+                (Some(Direction::UP), _) => '^',
+                (None, Tile::Space) => '.',
+                (None, Tile::Wall) => '#',
+                (None, Tile::Extern | Tile::Warp(_)) => ' ',
+            })
+        }
+        drawing.push('\n');
+    }
+
+    fs::write(out.join("walk.txt"), drawing)?;
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn guided(mut pos: Pos, field: &Grid<Tile>) -> Result<(), Box<dyn Error>> {
     stdout().into_raw_mode().unwrap();
@@ -610,9 +656,9 @@ fn guided(mut pos: Pos, field: &Grid<Tile>) -> Result<(), Box<dyn Error>> {
     for c in stdin.keys() {
         // plotting
         print!("{}", termion::clear::All);
-        for col in 0..field.cols() {
+        for col in 0..field.width() {
             print!("{}", termion::cursor::Goto(1, col as u16 + 1,),);
-            for (row, tile) in field.iter_col(col).enumerate().rev() {
+            for (row, tile) in field.column(col).enumerate().rev() {
                 print!(
                     "{}",
                     if (row, col) == pos.coord() {
@@ -650,8 +696,8 @@ fn guided(mut pos: Pos, field: &Grid<Tile>) -> Result<(), Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn plot(pos: Option<Pos>, field: &Grid<Tile>) {
-    for row in 0..field.rows() {
-        for (col, tile) in field.iter_row(row).enumerate() {
+    for row in 0..field.height() {
+        for (col, tile) in field.row(row).iter().enumerate() {
             if pos.is_some_and(|pos| (row, col) == (pos.row, pos.col)) {
                 match pos.unwrap().direction {
                     Direction::RIGHT => print!("👉"),
@@ -670,3 +716,20 @@ fn plot(pos: Option<Pos>, field: &Grid<Tile>) {
         println!();
     }
 }
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 22;
+    const TITLE: &'static str = "Monkey Map";
+    const VISUALIZATIONS: &'static phf::Map<&'static str, ReprFn> = &phf_map! {
+        "walk" => visualize_walk,
+    };
+
+    type Input<'a> = Notes;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
+    }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
+}
@@ -1,8 +1,6 @@
 use std::error::Error;
 
-use grid::Grid;
-
-use super::PuzzleResult;
+use super::{grid::Grid, Day, Params, PartFn, PuzzleResult, Solution};
 
 #[derive(Default, Clone, Copy, PartialEq, Eq)]
 enum Proposal {
@@ -13,43 +11,16 @@ enum Proposal {
 }
 
 fn parse_input(map: &str) -> Result<Grid<bool>, Box<dyn Error>> {
-    let height = map.lines().count();
-    let width = map.lines().map(|l| l.len()).max().unwrap_or(0);
-
-    let mut grid = Grid::new(height, width);
-    for (row, col, ch) in map.lines().enumerate().flat_map(|(row, line)| {
-        line.chars()
-            .enumerate()
-            .map(move |(col, ch)| (row, col, ch))
-    }) {
-        grid[row][col] = match ch {
-            '.' => false,
-            '#' => true,
-            ch => return Err(format!("{ch} is not a valid char").into()),
-        }
-    }
-
-    Ok(grid)
-}
-
-fn expand<T: Default + Copy>(grid: Grid<T>, border: usize) -> Grid<T> {
-    let new_rows = grid.rows() + border * 2;
-    let new_cols = grid.cols() + border * 2;
-
-    let mut new_grid = Grid::new(new_rows, new_cols);
-    for row in 0..grid.rows() {
-        let source_row = &grid[row];
-        let dest_row = &mut new_grid[row + border][border..new_cols - border];
-        // clone the row
-        dest_row.copy_from_slice(source_row)
-    }
-
-    new_grid
+    Ok(Grid::parse(map, false, "'.' or '#'", |_, ch| match ch {
+        '.' => Some(false),
+        '#' => Some(true),
+        _ => None,
+    })?)
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let mut grid = expand(parse_input(input)?, 10);
-    let mut proposals: Grid<Proposal> = Grid::new(grid.rows(), grid.cols());
+fn part1(grid: &Grid<bool>) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut grid = grid.clone().expand(10, false);
+    let mut proposals = Grid::new_like(&grid, Proposal::None);
     /*println!("== Initial State ==");
     print_grid(&grid);*/
     for i in 0..10 {
@@ -62,22 +33,14 @@ pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 
 fn step(grid: &mut Grid<bool>, proposals: &mut Grid<Proposal>, step: usize) -> bool {
     // first half
-    for row in 0..grid.rows() {
-        'col: for col in 0..grid.cols() {
-            if grid[row][col] {
+    for row in 0..grid.height() {
+        'col: for col in 0..grid.width() {
+            if grid[(col, row)] {
                 debug_assert!(
-                    1 <= row && row < grid.rows() - 1 && 1 <= col && col < grid.cols() - 1
+                    1 <= row && row < grid.height() - 1 && 1 <= col && col < grid.width() - 1
                 );
                 // check for neighbours
-                let mut neighbours = false;
-                for i in [row - 1, row, row + 1] {
-                    for j in [col - 1, col, col + 1] {
-                        if (i, j) != (row, col) && grid[i][j] {
-                            neighbours = true
-                        }
-                    }
-                }
-                if !neighbours {
+                if !grid.neighbours8((col, row)).any(|pos| grid[pos]) {
                     continue 'col;
                 }
                 // propose
@@ -108,7 +71,7 @@ fn step(grid: &mut Grid<bool>, proposals: &mut Grid<Proposal>, step: usize) -> b
 
                 'check_proposals: for j in 0..4 {
                     let (check_list, dest) = &p_list[(step + j) % 4];
-                    if check_list.iter().all(|(row, col)| !grid[*row][*col]) {
+                    if check_list.iter().all(|(row, col)| !grid[(*col, *row)]) {
                         proposal = Some(*dest);
                         break 'check_proposals;
                     };
@@ -116,7 +79,7 @@ fn step(grid: &mut Grid<bool>, proposals: &mut Grid<Proposal>, step: usize) -> b
 
                 if let Some((p_row, p_col)) = proposal {
                     use Proposal::*;
-                    proposals[p_row][p_col] = match proposals[p_row][p_col] {
+                    proposals[(p_col, p_row)] = match proposals[(p_col, p_row)] {
                         None => Some(row, col),
                         Some(_, _) => Blocked,
                         Blocked => Blocked,
@@ -127,14 +90,14 @@ fn step(grid: &mut Grid<bool>, proposals: &mut Grid<Proposal>, step: usize) -> b
     }
     // second half
     let mut something_moved = false;
-    for row in 0..proposals.rows() {
-        for col in 0..proposals.cols() {
-            if let Proposal::Some(s_row, s_col) = proposals[row][col] {
-                debug_assert!(grid[s_row][s_col]);
-                debug_assert!(!grid[row][col]);
+    for row in 0..proposals.height() {
+        for col in 0..proposals.width() {
+            if let Proposal::Some(s_row, s_col) = proposals[(col, row)] {
+                debug_assert!(grid[(s_col, s_row)]);
+                debug_assert!(!grid[(col, row)]);
                 // move elf
-                grid[s_row][s_col] = false;
-                grid[row][col] = true;
+                grid[(s_col, s_row)] = false;
+                grid[(col, row)] = true;
 
                 something_moved = true;
             }
@@ -149,18 +112,16 @@ fn step(grid: &mut Grid<bool>, proposals: &mut Grid<Proposal>, step: usize) -> b
 }
 
 fn borders(grid: &Grid<bool>) -> (usize, usize, usize, usize) {
-    let mut min_row = grid.rows();
+    let mut min_row = grid.height();
     let mut max_row = 0;
-    let mut min_col = grid.cols();
+    let mut min_col = grid.width();
     let mut max_col = 0;
-    for row in 0..grid.rows() {
-        for col in 0..grid.cols() {
-            if grid[row][col] {
-                min_row = min_row.min(row);
-                max_row = max_row.max(row + 1);
-                min_col = min_col.min(col);
-                max_col = max_col.max(col + 1);
-            }
+    for (col, row) in grid.positions() {
+        if grid[(col, row)] {
+            min_row = min_row.min(row);
+            max_row = max_row.max(row + 1);
+            min_col = min_col.min(col);
+            max_col = max_col.max(col + 1);
         }
     }
     (min_row, max_row, min_col, max_col)
@@ -171,7 +132,7 @@ fn count_empty(grid: &Grid<bool>, rect: (usize, usize, usize, usize)) -> usize {
     let mut count = 0;
     for row in min_row..max_row {
         for col in min_col..max_col {
-            if !grid[row][col] {
+            if !grid[(col, row)] {
                 count += 1;
             }
         }
@@ -179,22 +140,23 @@ fn count_empty(grid: &Grid<bool>, rect: (usize, usize, usize, usize)) -> usize {
     count
 }
 
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let mut grid = parse_input(input)?;
-    let mut proposals: Grid<Proposal> = Grid::new(grid.rows(), grid.cols());
+fn part2(grid: &Grid<bool>) -> Result<PuzzleResult, Box<dyn Error>> {
+    let mut grid = grid.clone();
+    let mut proposals = Grid::new_like(&grid, Proposal::None);
     /*println!("== Initial State ==");
     print_grid(&grid);*/
     for i in 0.. {
         // check if we need to enlarge the grid
         if grid
-            .iter_row(0)
-            .chain(grid.iter_row(grid.rows() - 1))
-            .chain(grid.iter_col(0))
-            .chain(grid.iter_col(grid.cols() - 1))
+            .row(0)
+            .iter()
+            .chain(grid.row(grid.height() - 1))
+            .chain(grid.column(0))
+            .chain(grid.column(grid.width() - 1))
             .any(|t| *t)
         {
-            grid = expand(grid, 10);
-            proposals = Grid::new(grid.rows(), grid.cols());
+            grid = grid.expand(10, false);
+            proposals = Grid::new_like(&grid, Proposal::None);
         }
         // step the simulation
         if !step(&mut grid, &mut proposals, i) {
@@ -206,16 +168,19 @@ pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
 
 #[allow(dead_code)]
 fn print_grid(grid: &Grid<bool>) {
-    for row in 0..grid.rows() {
-        for col in 0..grid.cols() {
-            print!(
-                "{}",
-                match grid[row][col] {
-                    false => '.',
-                    true => '#',
-                }
-            )
-        }
-        println!()
+    print!("{}", grid.map(|&elf| if elf { '#' } else { '.' }))
+}
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 23;
+    const TITLE: &'static str = "Unstable Diffusion";
+
+    type Input<'a> = Grid<bool>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        parse_input(input)
     }
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
 }
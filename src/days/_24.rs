@@ -1,38 +1,65 @@
-use std::{collections::BinaryHeap, error::Error};
+use std::error::Error;
 
 use arrayvec::ArrayVec;
-use grid::Grid;
 
-use super::PuzzleResult;
-
-fn parse_input(
-    input: &str,
-) -> Result<
-    (
-        (usize, usize),
-        (Grid<bool>, Grid<bool>, Grid<bool>, Grid<bool>),
-    ),
-    String,
-> {
-    let rows = input.trim().lines().count() - 2;
-    let cols = input
-        .trim()
-        .lines()
-        .map(|line| line.trim().len() - 2)
-        .max()
-        .unwrap();
+use super::{
+    grid::Grid,
+    search::{Graph, Search},
+    Day, Params, ParseError, PartFn, PuzzleResult, Solution,
+};
+
+/// Size of the valley, and where the blizzards going up, right, down and left are
+type Valley = (
+    (usize, usize),
+    (Grid<bool>, Grid<bool>, Grid<bool>, Grid<bool>),
+);
+
+fn parse_input(input: &str) -> Result<Valley, ParseError> {
+    let lines: Vec<&str> = input.trim().lines().map(str::trim).collect();
+    if lines.len() < 3 {
+        return Err(ParseError::at(input.trim(), "a valley between two walls"));
+    }
+    let width = lines[0].chars().count();
+    if width < 3 {
+        return Err(ParseError::at(lines[0], "a wall around the valley"));
+    }
+    let (rows, cols) = (lines.len() - 2, width - 2);
+    // entering on the top left, exiting on the bottom right
+    if lines[0] != format!("#.{}", "#".repeat(cols)) {
+        return Err(ParseError::at(
+            lines[0],
+            "a wall with the entrance on the left",
+        ));
+    }
+    if lines[rows + 1] != format!("{}.#", "#".repeat(cols)) {
+        return Err(ParseError::at(
+            lines[rows + 1],
+            "a wall with the exit on the right",
+        ));
+    }
     let [mut blizzard_up, mut blizzard_right, mut blizzard_down, mut blizzard_left] =
-        [(); 4].map(|_| Grid::new(rows, cols));
+        [(); 4].map(|_| Grid::new(cols, rows, false));
 
-    for (row, line) in input.trim().lines().skip(1).take(rows).enumerate() {
-        for (col, ch) in line.chars().skip(1).take(cols).enumerate() {
+    for (row, line) in lines[1..=rows].iter().enumerate() {
+        if line.chars().count() != width {
+            return Err(ParseError::at(line, format!("a row {width} wide")));
+        }
+        for (col, (idx, ch)) in line.char_indices().enumerate() {
+            let found = &line[idx..idx + ch.len_utf8()];
+            if col == 0 || col == width - 1 {
+                if ch != '#' {
+                    return Err(ParseError::at(found, "a wall"));
+                }
+                continue;
+            }
+            let pos = (col - 1, row);
             match ch {
-                '^' => blizzard_up[row][col] = true,
-                '>' => blizzard_right[row][col] = true,
-                'v' => blizzard_down[row][col] = true,
-                '<' => blizzard_left[row][col] = true,
+                '^' => blizzard_up[pos] = true,
+                '>' => blizzard_right[pos] = true,
+                'v' => blizzard_down[pos] = true,
+                '<' => blizzard_left[pos] = true,
                 '.' => (),
-                _ => return Err(format!("{ch} is not a valid input char")),
+                _ => return Err(ParseError::at(found, "a blizzard or .")),
             }
         }
     }
@@ -67,220 +94,163 @@ fn gcd(first: usize, second: usize) -> usize {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TileState {
-    Unexamined,
-    Blizzard,
-    Examined,
+/// Crossings of the valley, back and forth, while the blizzards blow
+///
+/// A state is the minute in the period of the blizzards, the row and column, and the number of
+/// crossings done. The cost is the time, in minutes
+struct Crossings {
+    rows: usize,
+    cols: usize,
+    /// blizzards are periodic, and so is the state space
+    period: usize,
+    crossings: usize,
+    /// Where the blizzards are at each minute of the period
+    blizzards: Box<[bool]>,
 }
-
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let ((rows, cols), (blizzard_up, blizzard_right, blizzard_down, blizzard_left)) =
-        parse_input(input)?;
-
-    // blizzards are periodic, and so is the state space
-    let period = lcm(rows, cols);
-
-    // create the possible state space memory
-    let mut state_space: Box<[TileState]> =
-        vec![TileState::Unexamined; period * rows * cols].into_boxed_slice();
-    // fill in the blizzards
-    for p in 0..period {
-        for r in 0..rows {
-            for c in 0..cols {
-                if blizzard_down[(period.div_ceil(rows) * rows + r - p) % rows][c]
-                    || blizzard_up[(r + p) % rows][c]
-                    || blizzard_right[r][(period.div_ceil(cols) * cols + c - p) % cols]
-                    || blizzard_left[r][(c + p) % cols]
-                {
-                    state_space[(p * rows + r) * cols + c] = TileState::Blizzard;
+type State = (usize, usize, usize, usize);
+
+impl Crossings {
+    fn new(valley: &Valley, crossings: usize) -> Self {
+        let &(
+            (rows, cols),
+            (ref blizzard_up, ref blizzard_right, ref blizzard_down, ref blizzard_left),
+        ) = valley;
+        let period = lcm(rows, cols);
+
+        // fill in the blizzards
+        let mut blizzards = vec![false; period * rows * cols].into_boxed_slice();
+        for p in 0..period {
+            for r in 0..rows {
+                for c in 0..cols {
+                    blizzards[(p * rows + r) * cols + c] = blizzard_down
+                        [(c, (period.div_ceil(rows) * rows + r - p) % rows)]
+                        || blizzard_up[(c, (r + p) % rows)]
+                        || blizzard_right[((period.div_ceil(cols) * cols + c - p) % cols, r)]
+                        || blizzard_left[((c + p) % cols, r)];
                 }
             }
         }
+        Self {
+            rows,
+            cols,
+            period,
+            crossings,
+            blizzards,
+        }
     }
 
-    // euristic for the A*
-    let euristic = |_p: usize, r: usize, c: usize| -> usize { 1 + (rows - 1 - r) + (cols - 1 - c) };
-
-    // create the states queue
-    let mut states_to_examine = BinaryHeap::new();
+    fn blizzard(&self, p: usize, r: usize, c: usize) -> bool {
+        self.blizzards[(p * self.rows + r) * self.cols + c]
+    }
 
-    // adding the possible enter states
-    for enter in 1..=period {
-        if state_space[(enter % period * rows + 0) * cols + 0] != TileState::Blizzard {
-            states_to_examine.push((
-                -((enter + euristic(enter % period, 0, 0)) as isize),
-                (enter, 0usize, 0usize),
-            ))
+    /// The tile next to the exit of a crossing
+    fn exit(&self, crossing: usize) -> (usize, usize) {
+        if crossing % 2 == 0 {
+            (self.rows - 1, self.cols - 1)
+        } else {
+            (0, 0)
         }
     }
 
-    while let Some((_, (m, r, c))) = states_to_examine.pop() {
-        debug_assert_ne!(
-            state_space[(m % period * rows + r) * cols + c],
-            TileState::Blizzard
-        );
-        // did we arrive?
-        if r == rows - 1 && c == cols - 1 {
+    /// Minutes needed at least to end all the crossings
+    fn euristic(&self, &(_, r, c, crossing): &State) -> u32 {
+        let (exit_r, exit_c) = self.exit(crossing);
+        minutes(
+            exit_r.abs_diff(r)
+                + exit_c.abs_diff(c)
+                + 1
+                + (self.crossings - 1 - crossing) * (self.rows - 1 + self.cols - 1 + 2),
+        )
+    }
+
+    /// Cross the valley, returning the minutes taken
+    fn cross(&self) -> Result<PuzzleResult, Box<dyn Error>> {
+        // adding the possible enter states
+        let enters = (1..=self.period)
+            .filter(|enter| !self.blizzard(enter % self.period, 0, 0))
+            .map(|enter| ((enter % self.period, 0, 0, 0), minutes(enter)));
+
+        Search::new(self)
+            .a_star(
+                enters,
+                |&(_, r, c, crossing)| {
+                    crossing == self.crossings - 1 && (r, c) == self.exit(crossing)
+                },
+                |state| self.euristic(state),
+            )
             // adding 1 to count the exit step
-            return Ok(PuzzleResult::Numeric((m + 1) as _));
-        }
-        // pruning
-        if state_space[(m % period * rows + r) * cols + c] == TileState::Examined {
-            // someone was already here. Pruning...
-            continue;
-        }
-        // mark as examined
-        state_space[(m % period * rows + r) * cols + c] = TileState::Examined;
+            .map(|(_, minutes)| PuzzleResult::Numeric(i64::from(minutes) + 1))
+            // we hit dead end everywhere...
+            .ok_or_else(|| "No path found...".into())
+    }
+}
+/// A number of minutes as a cost of the search
+fn minutes(n: usize) -> u32 {
+    u32::try_from(n).expect("The minutes should fit in the costs")
+}
 
-        // enumerate the neighbours
-        let mut neighbours: ArrayVec<_, 5> = ArrayVec::new();
-        neighbours.push((m + 1, r, c));
+impl Graph for Crossings {
+    type Node = State;
+    type Cost = u32;
+
+    fn size(&self) -> usize {
+        self.crossings * self.period * self.rows * self.cols
+    }
+    fn index(&self, &(p, r, c, crossing): &State) -> usize {
+        ((crossing * self.period + p) * self.rows + r) * self.cols + c
+    }
+    fn neighbours(&self, &(p, r, c, crossing): &State, mut edge: impl FnMut(State, u32)) {
+        let next = (p + 1) % self.period;
+        // enumerate the moves
+        let mut moves: ArrayVec<_, 5> = ArrayVec::new();
+        moves.push((r, c));
         if r > 0 {
-            neighbours.push((m + 1, r - 1, c))
+            moves.push((r - 1, c))
         }
-        if r < rows - 1 {
-            neighbours.push((m + 1, r + 1, c))
+        if r < self.rows - 1 {
+            moves.push((r + 1, c))
         }
         if c > 0 {
-            neighbours.push((m + 1, r, c - 1))
+            moves.push((r, c - 1))
         }
-        if c < cols - 1 {
-            neighbours.push((m + 1, r, c + 1))
+        if c < self.cols - 1 {
+            moves.push((r, c + 1))
         }
-
-        // filtering the neighbours and examine the path extension
-        for (m, r, c) in neighbours {
-            // is this a new possible branch?
-            if state_space[(m % period * rows + r) * cols + c] == TileState::Unexamined {
-                // add the path to the one needing further examining
-                states_to_examine.push((-((m + euristic(m % period, r, c)) as isize), (m, r, c)))
+        for (r, c) in moves {
+            if !self.blizzard(next, r, c) {
+                edge((next, r, c, crossing), 1)
             }
         }
-    }
-
-    // we hit dead end everywhere...
-    Err("No path found...".into())
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum TileState2 {
-    Blizzard,
-    Space([bool; 3]),
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Traverse {
-    First = 0,
-    Back = 1,
-    Return = 2,
-}
-
-pub fn part2(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    let ((rows, cols), (blizzard_up, blizzard_right, blizzard_down, blizzard_left)) =
-        parse_input(input)?;
-
-    // blizzards are periodic, and so is the state space
-    let period = lcm(rows, cols);
-
-    // create the possible state space memory
-    let mut state_space: Box<[TileState2]> =
-        vec![TileState2::Space([false; 3]); period * rows * cols].into_boxed_slice();
-    // fill in the blizzards
-    for p in 0..period {
-        for r in 0..rows {
-            for c in 0..cols {
-                if blizzard_down[(period.div_ceil(rows) * rows + r - p) % rows][c]
-                    || blizzard_up[(r + p) % rows][c]
-                    || blizzard_right[r][(period.div_ceil(cols) * cols + c - p) % cols]
-                    || blizzard_left[r][(c + p) % cols]
-                {
-                    state_space[(p * rows + r) * cols + c] = TileState2::Blizzard;
+        // stepping out at the end of a crossing, and in again for the next one
+        if crossing + 1 < self.crossings && (r, c) == self.exit(crossing) {
+            for wait in 2..2 + self.period {
+                let p = (p + wait) % self.period;
+                if !self.blizzard(p, r, c) {
+                    edge((p, r, c, crossing + 1), minutes(wait))
                 }
             }
         }
     }
+}
 
-    // euristic for the A*
-    let euristic = |_p: usize, r: usize, c: usize, traverse: Traverse| -> usize {
-        match traverse {
-            Traverse::First => 2 * (rows - 1 + cols - 1 + 2) + 1 + (rows - 1 - r) + (cols - 1 - c),
-            Traverse::Back => (rows - 1 + cols - 1 + 2) + 1 + r + c,
-            Traverse::Return => 1 + (rows - 1 - r) + (cols - 1 - c),
-        }
-    };
+fn part1(valley: &Valley) -> Result<PuzzleResult, Box<dyn Error>> {
+    Crossings::new(valley, 1).cross()
+}
 
-    // create the states queue
-    let mut states_to_examine = BinaryHeap::new();
+fn part2(valley: &Valley) -> Result<PuzzleResult, Box<dyn Error>> {
+    Crossings::new(valley, 3).cross()
+}
 
-    // adding the possible enter states
-    for enter in 1..=period {
-        if state_space[(enter % period * rows + 0) * cols + 0] != TileState2::Blizzard {
-            states_to_examine.push((
-                -((enter + euristic(enter % period, 0, 0, Traverse::First)) as isize),
-                (enter, 0usize, 0usize, Traverse::First),
-            ))
-        }
-    }
+pub static SOLUTION: &dyn Day = &Puzzle;
 
-    while let Some((_, (m, r, c, traverse))) = states_to_examine.pop() {
-        // did we arrive?
-        if r == rows - 1 && c == cols - 1 && traverse == Traverse::Return {
-            // adding 1 to count the exit step
-            return Ok(PuzzleResult::Numeric((m + 1) as _));
-        }
-        // pruning
-        match &mut state_space[(m % period * rows + r) * cols + c] {
-            TileState2::Space(v) if v[traverse as usize] => continue, // somebody was there before, in the same traverse
-            TileState2::Space(v) => v[traverse as usize] = true,      // mark as examined
-            TileState2::Blizzard => unreachable!(),
-        }
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 24;
+    const TITLE: &'static str = "Blizzard Basin";
 
-        // enumerate the neighbours
-        let mut neighbours: ArrayVec<_, 5> = ArrayVec::new();
-        neighbours.push((m + 1, r, c, traverse));
-        if r > 0 {
-            neighbours.push((m + 1, r - 1, c, traverse))
-        }
-        if r < rows - 1 {
-            neighbours.push((m + 1, r + 1, c, traverse))
-        }
-        if c > 0 {
-            neighbours.push((m + 1, r, c - 1, traverse))
-        }
-        if c < cols - 1 {
-            neighbours.push((m + 1, r, c + 1, traverse))
-        }
-        // adding the first stop
-        let neighbours = neighbours.into_iter().chain(
-            (traverse == Traverse::First && r == rows - 1 && c == cols - 1)
-                .then(|| ((m + 2)..(m + 2 + period)).map(|m| (m, r, c, Traverse::Back)))
-                .into_iter()
-                .flatten(),
-        );
-        // adding the second stop
-        let neighbours = neighbours.chain(
-            (traverse == Traverse::Back && r == 0 && c == 0)
-                .then(|| ((m + 2)..(m + 2 + period)).map(|m| (m, r, c, Traverse::Return)))
-                .into_iter()
-                .flatten(),
-        );
-
-        // filtering the neighbours and examine the path extension
-        for (m, r, c, traverse) in neighbours {
-            // is this a new possible branch?
-            if let TileState2::Space(v) = &state_space[(m % period * rows + r) * cols + c] {
-                if !v[traverse as usize] {
-                    // add the path to the one needing further examining
-                    states_to_examine.push((
-                        -((m + euristic(m % period, r, c, traverse)) as isize),
-                        (m, r, c, traverse),
-                    ))
-                }
-            }
-        }
+    type Input<'a> = Valley;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
+        Ok(parse_input(input)?)
     }
-
-    // we hit dead end everywhere...
-    Err("No path found...".into())
+    const PARTS: &'static [PartFn<Self>] = &[part1, part2];
 }
@@ -1,28 +1,29 @@
 use std::error::Error;
 
-use super::PuzzleResult;
+use super::{Day, Params, ParseError, PartFn, PuzzleResult, Solution};
 
-fn parse_snafu(val: &str) -> Result<isize, Box<dyn Error>> {
-    val.chars()
-        .rev()
-        .enumerate()
-        .map(|(pow, ch)| {
-            match ch {
-                '2' => Ok(2),
-                '1' => Ok(1),
-                '0' => Ok(0),
-                '-' => Ok(-1),
-                '=' => Ok(-2),
-                ch => Err(format!("{ch:?} is not a valid SNAFU digit")),
+fn parse_snafu(val: &str) -> Result<isize, ParseError> {
+    if val.is_empty() {
+        return Err(ParseError::at(val, "a SNAFU number"));
+    }
+    val.char_indices().try_fold(0isize, |sum, (idx, ch)| {
+        let digit = match ch {
+            '2' => 2,
+            '1' => 1,
+            '0' => 0,
+            '-' => -1,
+            '=' => -2,
+            ch => {
+                return Err(ParseError::at(
+                    &val[idx..idx + ch.len_utf8()],
+                    "a SNAFU digit",
+                ))
             }
-            .and_then(|v| {
-                5isize
-                    .checked_pow(pow as u32)
-                    .ok_or_else(|| "SNAFU number too big".to_owned())
-                    .map(|exp| v * exp)
-            })
-        })
-        .try_fold(0, |sum, x| x.map(|x| x + sum).map_err(|err| err.into()))
+        };
+        sum.checked_mul(5)
+            .and_then(|sum| sum.checked_add(digit))
+            .ok_or_else(|| ParseError::at(val, "a smaller SNAFU number"))
+    })
 }
 fn format_snafu(n: isize) -> String {
     let (last_digit, borrow) = match n.rem_euclid(5) {
@@ -43,12 +44,24 @@ fn format_snafu(n: isize) -> String {
     pre
 }
 
-pub fn part1(input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
-    Ok(PuzzleResult::Textual(format_snafu(
+fn part1(numbers: &[isize]) -> Result<PuzzleResult, Box<dyn Error>> {
+    Ok(PuzzleResult::Textual(format_snafu(numbers.iter().sum())))
+}
+
+pub static SOLUTION: &dyn Day = &Puzzle;
+
+struct Puzzle;
+impl Solution for Puzzle {
+    const DAY: u8 = 25;
+    const TITLE: &'static str = "Full of Hot Air";
+
+    type Input<'a> = Vec<isize>;
+    fn parse<'a>(input: &'a str, _params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>> {
         input
             .trim()
             .lines()
-            .map(|line| parse_snafu(line.trim()))
-            .try_fold(0, |sum, x| x.map(|x| x + sum))?,
-    )))
+            .map(|line| parse_snafu(line.trim()).map_err(|err| err.into()))
+            .collect()
+    }
+    const PARTS: &'static [PartFn<Self>] = &[|input| part1(input)];
 }
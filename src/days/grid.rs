@@ -0,0 +1,295 @@
+//! A rectangular grid of cells, for the days working on maps
+//!
+//! Positions are `(x, y)` pairs, with `(0, 0)` the top left cell. They can be unsigned, or signed
+//! to step out of the grid without wrapping around
+
+use std::{
+    fmt::{self, Debug, Display},
+    ops::{Index, IndexMut},
+};
+
+use super::ParseError;
+
+/// A coordinate of a position in a grid
+pub trait Coord: Copy + PartialEq + Debug + 'static {
+    /// The coordinate as an index, if it is not negative
+    fn to_index(self) -> Option<usize>;
+    /// Move by `delta`, if the result can be represented
+    fn offset(self, delta: isize) -> Option<Self>;
+}
+impl Coord for usize {
+    fn to_index(self) -> Option<usize> {
+        Some(self)
+    }
+    fn offset(self, delta: isize) -> Option<Self> {
+        self.checked_add_signed(delta)
+    }
+}
+impl Coord for isize {
+    fn to_index(self) -> Option<usize> {
+        usize::try_from(self).ok()
+    }
+    fn offset(self, delta: isize) -> Option<Self> {
+        self.checked_add(delta)
+    }
+}
+
+/// Steps to the 4 orthogonal neighbours, clockwise from the top
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+/// Steps to the 8 neighbours, clockwise from the top
+const ALL_AROUND: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// The cells, row by row
+    cells: Box<[T]>,
+}
+
+impl<T> Grid<T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    /// Width and height
+    pub fn shape(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn cell_index<C: Coord>(&self, (x, y): (C, C)) -> Option<usize> {
+        let (x, y) = (x.to_index()?, y.to_index()?);
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    /// Whether a position is inside the grid
+    pub fn contains<C: Coord>(&self, pos: (C, C)) -> bool {
+        self.cell_index(pos).is_some()
+    }
+    pub fn get<C: Coord>(&self, pos: (C, C)) -> Option<&T> {
+        self.cell_index(pos).map(|idx| &self.cells[idx])
+    }
+    pub fn get_mut<C: Coord>(&mut self, pos: (C, C)) -> Option<&mut T> {
+        self.cell_index(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// All the cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+    /// All the positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "Column {x} is out of the grid!");
+        self.cells[x..].iter().step_by(self.width.max(1))
+    }
+
+    /// The orthogonal neighbours of a position that are inside the grid
+    pub fn neighbours4<C: Coord>(&self, pos: (C, C)) -> impl Iterator<Item = (C, C)> + '_ {
+        self.around(pos, &ORTHOGONAL)
+    }
+    /// The neighbours of a position that are inside the grid, diagonals included
+    pub fn neighbours8<C: Coord>(&self, pos: (C, C)) -> impl Iterator<Item = (C, C)> + '_ {
+        self.around(pos, &ALL_AROUND)
+    }
+    fn around<C: Coord>(
+        &self,
+        (x, y): (C, C),
+        steps: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (C, C)> + '_ {
+        steps
+            .iter()
+            .filter_map(move |&(dx, dy)| Some((x.offset(dx)?, y.offset(dy)?)))
+            .filter(move |&pos| self.contains(pos))
+    }
+
+    /// A grid of the same shape, with `f` applied to each cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height].into_boxed_slice(),
+        }
+    }
+    pub fn new_like<O>(other: &Grid<O>, fill: T) -> Self {
+        Self::new(other.width, other.height, fill)
+    }
+
+    /// Read a grid from a map, with a line for each row
+    ///
+    /// `cell` reads the character at a position, and the short lines are completed with `fill`
+    pub fn parse(
+        map: &str,
+        fill: T,
+        expected: &str,
+        mut cell: impl FnMut((usize, usize), char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = map.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+        let mut grid = Self::new(width, map.lines().count(), fill);
+        for (y, line) in map.lines().enumerate() {
+            for (x, (idx, ch)) in line.char_indices().enumerate() {
+                grid[(x, y)] = cell((x, y), ch)
+                    .ok_or_else(|| ParseError::at(&line[idx..idx + ch.len_utf8()], expected))?;
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn fill(&mut self, value: T) {
+        self.cells.fill(value)
+    }
+
+    /// Add `border` cells of `fill` on each side, moving the others by `(border, border)`
+    pub fn expand(self, border: usize, fill: T) -> Self {
+        let mut expanded = Self::new(self.width + 2 * border, self.height + 2 * border, fill);
+        for y in 0..self.height {
+            let start = (y + border) * expanded.width + border;
+            expanded.cells[start..start + self.width].clone_from_slice(self.row(y))
+        }
+        expanded
+    }
+}
+
+impl<T, C: Coord> Index<(C, C)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (C, C)) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Position {pos:?} is out of the grid!"))
+    }
+}
+impl<T, C: Coord> IndexMut<(C, C)> for Grid<T> {
+    fn index_mut(&mut self, pos: (C, C)) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Position {pos:?} is out of the grid!"))
+    }
+}
+
+/// Draw the grid, a line for each row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Digits, with `.` for empty cells
+    fn digits(map: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(map, 0, "a digit or .", |_, ch| match ch {
+            '.' => Some(0),
+            ch => ch.to_digit(10).map(|d| d as u8),
+        })
+    }
+
+    #[test]
+    fn parse() {
+        let grid = digits("123\n4\n.56\n").unwrap();
+        assert_eq!(grid.shape(), (3, 3));
+        // the short lines are filled
+        assert_eq!(grid.row(0), &[1, 2, 3]);
+        assert_eq!(grid.row(1), &[4, 0, 0]);
+        assert_eq!(grid.row(2), &[0, 5, 6]);
+        assert_eq!(grid[(2usize, 0usize)], 3);
+        assert_eq!(grid.get((-1isize, 0isize)), None);
+        assert_eq!(grid.get((3usize, 0usize)), None);
+
+        let map = "123\n4x6\n";
+        let err = digits(map).unwrap_err().locate(1, map);
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.found, "\"x\"");
+        // the column counts chars, not bytes
+        let map = "12\n\u{e9}\u{e9}\n";
+        let err = digits(map).unwrap_err().locate(1, map);
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.found, "\"\u{e9}\"");
+    }
+
+    #[test]
+    fn expand() {
+        let grid = digits("12\n34").unwrap().expand(1, 9);
+        assert_eq!(grid.shape(), (4, 4));
+        assert_eq!(grid.row(0), &[9, 9, 9, 9]);
+        assert_eq!(grid.row(1), &[9, 1, 2, 9]);
+        assert_eq!(grid.row(2), &[9, 3, 4, 9]);
+        assert_eq!(grid.row(3), &[9, 9, 9, 9]);
+        assert_eq!(grid.expand(0, 0).shape(), (4, 4));
+    }
+
+    #[test]
+    fn column() {
+        let grid = digits("123\n456").unwrap();
+        assert_eq!(grid.column(0).copied().collect::<Vec<_>>(), vec![1, 4]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(
+            grid.column(1).rev().copied().collect::<Vec<_>>(),
+            vec![5, 2]
+        );
+        assert_eq!(grid.column(1).len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Column 3 is out of the grid!")]
+    fn column_out_of_the_grid() {
+        // without the check, this would start from the second row
+        digits("123\n456").unwrap().column(3).count();
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 2, ());
+        let around = |pos: (usize, usize)| grid.neighbours4(pos).collect::<Vec<_>>();
+        assert_eq!(around((0, 0)), vec![(1, 0), (0, 1)]);
+        assert_eq!(around((1, 0)), vec![(2, 0), (1, 1), (0, 0)]);
+        assert_eq!(around((2, 1)), vec![(2, 0), (1, 1)]);
+        let around = |pos: (usize, usize)| grid.neighbours8(pos).collect::<Vec<_>>();
+        assert_eq!(around((0, 0)), vec![(1, 0), (1, 1), (0, 1)]);
+        assert_eq!(around((1, 1)), vec![(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]);
+
+        // signed positions can be outside the grid
+        let around = |pos: (isize, isize)| grid.neighbours4(pos).collect::<Vec<_>>();
+        assert_eq!(around((0, 0)), vec![(1, 0), (0, 1)]);
+        assert_eq!(around((-1, 0)), vec![(0, 0)]);
+        assert_eq!(around((3, 1)), vec![(2, 1)]);
+        assert_eq!(around((5, 5)), vec![]);
+        let around = |pos: (isize, isize)| grid.neighbours8(pos).collect::<Vec<_>>();
+        assert_eq!(around((2, 1)), vec![(2, 0), (1, 1), (1, 0)]);
+        assert_eq!(around((-1, -1)), vec![(0, 0)]);
+        assert_eq!(around((3, 2)), vec![(2, 1)]);
+    }
+}
@@ -1,4 +1,10 @@
-use std::{error::Error, path::Path};
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display},
+    path::Path,
+    str::FromStr,
+};
 
 use phf_macros::phf_map;
 
@@ -28,10 +34,20 @@ mod _23;
 mod _24;
 mod _25;
 
+mod grid;
+pub mod search;
+
 pub enum ResultRepr {
     Short(String),
     Multiline(String),
 }
+impl ResultRepr {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ResultRepr::Short(s) | ResultRepr::Multiline(s) => s,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub enum PuzzleResult {
@@ -48,39 +64,253 @@ impl PuzzleResult {
             PuzzleResult::AsciiArt(s) => Multiline(s.clone()),
         }
     }
+
+    /// Name of the kind of result
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PuzzleResult::Numeric(_) => "numeric",
+            PuzzleResult::Textual(_) => "textual",
+            PuzzleResult::AsciiArt(_) => "ascii_art",
+        }
+    }
+}
+
+/// Parameters of the puzzles, given as `name=value` pairs
+///
+/// Each day declares the ones it reads in [`Solution::PARAMS`], with the values of the real puzzle
+/// as defaults
+#[derive(Debug, Clone, Default)]
+pub struct Params(HashMap<String, String>);
+impl Params {
+    /// The default values of some parameters
+    pub fn defaults(declared: &[Param]) -> Self {
+        Self(
+            declared
+                .iter()
+                .map(|p| (p.name.to_owned(), p.default.to_owned()))
+                .collect(),
+        )
+    }
+
+    pub fn set(&mut self, name: String, value: String) {
+        self.0.insert(name, value);
+    }
+
+    /// All the parameters set, as `(name, value)`
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    /// Get a parameter
+    pub fn get<T>(&self, name: &str) -> Result<T, Box<dyn Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .0
+            .get(name)
+            .ok_or_else(|| format!("Missing parameter {name}"))?;
+        value
+            .parse()
+            .map_err(|err| format!("Invalid value {value:?} for parameter {name}: {err}").into())
+    }
+}
+
+/// Declaration of a parameter of a puzzle
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    pub name: &'static str,
+    /// Value in the real puzzle
+    pub default: &'static str,
+    pub description: &'static str,
 }
 
-pub type SolveFn = fn(&str) -> Result<PuzzleResult, Box<dyn Error>>;
-pub type ReprFn = fn(&str, Path) -> Result<(), Box<dyn Error>>;
-
-pub const DAYS: [(
-    Option<SolveFn>,
-    Option<SolveFn>,
-    phf::Map<&'static str, ReprFn>,
-); 25] = [
-    (Some(_01::part1), Some(_01::part2), phf_map! {}),
-    (Some(_02::part1), Some(_02::part2), phf_map! {}),
-    (Some(_03::part1), Some(_03::part2), phf_map! {}),
-    (Some(_04::part1), Some(_04::part2), phf_map! {}),
-    (Some(_05::part1), Some(_05::part2), phf_map! {}),
-    (Some(_06::part1), Some(_06::part2), phf_map! {}),
-    (Some(_07::part1), Some(_07::part2), phf_map! {}),
-    (Some(_08::part1), Some(_08::part2), phf_map! {}),
-    (Some(_09::part1), Some(_09::part2), phf_map! {}),
-    (Some(_10::part1), Some(_10::part2), phf_map! {}),
-    (Some(_11::part1), Some(_11::part2), phf_map! {}),
-    (Some(_12::part1), Some(_12::part2), phf_map! {}),
-    (Some(_13::part1), Some(_13::part2), phf_map! {}),
-    (Some(_14::part1), Some(_14::part2), phf_map! {}),
-    (Some(_15::part1), Some(_15::part2), phf_map! {}),
-    (Some(_16::part1), Some(_16::part2), phf_map! {}),
-    (Some(_17::part1), Some(_17::part2), phf_map! {}),
-    (Some(_18::part1), Some(_18::part2), phf_map! {}),
-    (Some(_19::part1), Some(_19::part2), phf_map! {}),
-    (Some(_20::part1), Some(_20::part2), phf_map! {}),
-    (Some(_21::part1), Some(_21::part2), phf_map! {}),
-    (Some(_22::part1), Some(_22::part2), phf_map! {}),
-    (Some(_23::part1), Some(_23::part2), phf_map! {}),
-    (Some(_24::part1), Some(_24::part2), phf_map! {}),
-    (Some(_25::part1), None, phf_map! {}),
+/// An error in the input of a puzzle
+///
+/// The days point at the wrong part of the input with [`ParseError::at`], and the day, line and
+/// column are filled in when the error comes out of [`Day::parse`] or [`Parsed::solve`]
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub day: u8,
+    /// Line of the error, starting from 1, or 0 if it is not known
+    pub line: usize,
+    /// Column of the error in characters, starting from 1
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+    /// Address of the wrong part of the input
+    address: usize,
+}
+impl ParseError {
+    /// Error on `found`, that must be a slice of the input
+    pub fn at(found: &str, expected: impl Into<String>) -> Self {
+        Self {
+            day: 0,
+            line: 0,
+            column: 0,
+            expected: expected.into(),
+            found: if found.is_empty() {
+                "nothing".to_owned()
+            } else {
+                format!("{found:?}")
+            },
+            address: found.as_ptr() as usize,
+        }
+    }
+
+    /// Find the position of the error in the input
+    fn locate(mut self, day: u8, input: &str) -> Self {
+        self.day = day;
+        let offset = self.address.wrapping_sub(input.as_ptr() as usize);
+        if let Some(before) = input.get(..offset) {
+            let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+            self.line = before.matches('\n').count() + 1;
+            self.column = before[line_start..].chars().count() + 1;
+        }
+        self
+    }
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {}", self.day)?;
+        if self.line > 0 {
+            write!(f, ", line {}, column {}", self.line, self.column)?;
+        }
+        write!(f, ": expected {}, found {}", self.expected, self.found)
+    }
+}
+impl Error for ParseError {}
+
+/// Parse a slice of the input, failing with a [`ParseError`] on it
+pub fn parse_at<T: FromStr>(found: &str, expected: &str) -> Result<T, ParseError> {
+    found.parse().map_err(|_| ParseError::at(found, expected))
+}
+
+/// Fill in the position of the parse errors
+fn locate_error(err: Box<dyn Error>, day: u8, input: &str) -> Box<dyn Error> {
+    match err.downcast::<ParseError>() {
+        Ok(err) => Box::new(err.locate(day, input)),
+        Err(err) => err,
+    }
+}
+
+/// A visualization of a day: gets the input and the directory where it should write its outputs
+pub type ReprFn = fn(&str, &Path) -> Result<(), Box<dyn Error>>;
+
+/// A part of a solution, working on the parsed input
+pub type PartFn<S> =
+    for<'a, 'b> fn(&'b <S as Solution>::Input<'a>) -> Result<PuzzleResult, Box<dyn Error>>;
+
+/// The solution of a day
+pub trait Solution: Sync + 'static {
+    const DAY: u8;
+    const TITLE: &'static str;
+    /// Parameters read by `parse`
+    const PARAMS: &'static [Param] = &[];
+    const VISUALIZATIONS: &'static phf::Map<&'static str, ReprFn> = &phf_map! {};
+
+    /// The input, parsed
+    type Input<'a>: Send + Sync;
+    fn parse<'a>(input: &'a str, params: &Params) -> Result<Self::Input<'a>, Box<dyn Error>>;
+    /// The parts solved, in order
+    const PARTS: &'static [PartFn<Self>];
+}
+
+/// A solution, with the type of its input erased
+pub trait Day: Sync {
+    fn number(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn params(&self) -> &'static [Param];
+    fn visualizations(&self) -> &'static phf::Map<&'static str, ReprFn>;
+    /// Number of parts solved
+    fn parts(&self) -> usize;
+    /// Parse the input, filling in the default parameters
+    fn parse<'a>(
+        &self,
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Box<dyn Parsed + 'a>, Box<dyn Error>>;
+}
+
+/// A parsed input, ready to be solved
+pub trait Parsed: Send + Sync {
+    /// Solve a part (starting from 1)
+    fn solve(&self, part: usize) -> Result<PuzzleResult, Box<dyn Error>>;
+}
+
+/// The parsed input, with the input to locate the errors of the parts that read it on their own
+struct ParsedInput<'a, S: Solution>(S::Input<'a>, &'a str);
+impl<'a, S: Solution> Parsed for ParsedInput<'a, S> {
+    fn solve(&self, part: usize) -> Result<PuzzleResult, Box<dyn Error>> {
+        let solve = S::PARTS
+            .get(part.wrapping_sub(1))
+            .ok_or_else(|| format!("Day {} has no part {part}", S::DAY))?;
+        solve(&self.0).map_err(|err| locate_error(err, S::DAY, self.1))
+    }
+}
+
+impl<S: Solution> Day for S {
+    fn number(&self) -> u8 {
+        S::DAY
+    }
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+    fn params(&self) -> &'static [Param] {
+        S::PARAMS
+    }
+    fn visualizations(&self) -> &'static phf::Map<&'static str, ReprFn> {
+        S::VISUALIZATIONS
+    }
+    fn parts(&self) -> usize {
+        S::PARTS.len()
+    }
+    fn parse<'a>(
+        &self,
+        input: &'a str,
+        params: &Params,
+    ) -> Result<Box<dyn Parsed + 'a>, Box<dyn Error>> {
+        let mut all_params = Params::defaults(S::PARAMS);
+        all_params.0.extend(params.0.clone());
+        let parsed =
+            S::parse(input, &all_params).map_err(|err| locate_error(err, S::DAY, input))?;
+        Ok(Box::new(ParsedInput::<S>(parsed, input)))
+    }
+}
+
+/// All the solved days, in order
+pub static DAYS: [&dyn Day; 25] = [
+    _01::SOLUTION,
+    _02::SOLUTION,
+    _03::SOLUTION,
+    _04::SOLUTION,
+    _05::SOLUTION,
+    _06::SOLUTION,
+    _07::SOLUTION,
+    _08::SOLUTION,
+    _09::SOLUTION,
+    _10::SOLUTION,
+    _11::SOLUTION,
+    _12::SOLUTION,
+    _13::SOLUTION,
+    _14::SOLUTION,
+    _15::SOLUTION,
+    _16::SOLUTION,
+    _17::SOLUTION,
+    _18::SOLUTION,
+    _19::SOLUTION,
+    _20::SOLUTION,
+    _21::SOLUTION,
+    _22::SOLUTION,
+    _23::SOLUTION,
+    _24::SOLUTION,
+    _25::SOLUTION,
 ];
+
+/// Find the solution of a day
+pub fn get(day: u8) -> Option<&'static dyn Day> {
+    DAYS.iter().copied().find(|d| d.number() == day)
+}
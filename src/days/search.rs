@@ -0,0 +1,344 @@
+//! Searches of the shortest paths in graphs, for the days exploring mazes and state spaces
+//!
+//! The graphs are implicit: a [`Graph`] only tells the moves from a node, and numbers the nodes
+//! so the searches can keep what they found in flat tables
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, VecDeque},
+    ops::Add,
+};
+
+/// A cost of the moves, summed along the paths
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+pub trait Graph {
+    type Node: Clone;
+    type Cost: Cost;
+
+    /// Number of nodes, all the indices being below it
+    fn size(&self) -> usize;
+    /// Index of a node. Nodes sharing an index are the same for the search
+    fn index(&self, node: &Self::Node) -> usize;
+    /// Call `edge` on each node reachable from `node` in one move, with the cost of the move
+    fn neighbours(&self, node: &Self::Node, edge: impl FnMut(Self::Node, Self::Cost));
+}
+
+/// A node waiting in the queue of Dijkstra and A*, the lowest priority first
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+impl<N, C: Ord> Eq for Queued<N, C> {}
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// A search on a graph, keeping the lowest cost found to each node
+pub struct Search<'g, G: Graph> {
+    graph: &'g G,
+    costs: Vec<Option<G::Cost>>,
+    /// Node each one was reached from, if the paths are recorded
+    came_from: Option<Vec<Option<G::Node>>>,
+}
+
+impl<'g, G: Graph> Search<'g, G> {
+    pub fn new(graph: &'g G) -> Self {
+        Self {
+            graph,
+            costs: vec![None; graph.size()],
+            came_from: None,
+        }
+    }
+
+    /// Record where each node was reached from, to get the paths with [`Search::path`]
+    pub fn recording_paths(mut self) -> Self {
+        self.came_from = Some(vec![None; self.graph.size()]);
+        self
+    }
+
+    /// Lowest cost found to a node, if it was reached
+    pub fn cost(&self, node: &G::Node) -> Option<G::Cost> {
+        self.costs[self.graph.index(node)]
+    }
+
+    /// Path from a start to a reached node, both included
+    ///
+    /// Panics if the paths are not recorded
+    pub fn path(&self, node: &G::Node) -> Vec<G::Node> {
+        let came_from = self
+            .came_from
+            .as_ref()
+            .expect("The paths were not recorded");
+        let mut path = vec![node.clone()];
+        while let Some(prev) = &came_from[self.graph.index(path.last().unwrap())] {
+            path.push(prev.clone())
+        }
+        path.reverse();
+        path
+    }
+
+    /// Explore the nodes in order of number of moves from the starts, until one is a goal
+    ///
+    /// The costs are summed as usual, so they are the lowest only if all moves cost the same
+    pub fn bfs(
+        &mut self,
+        starts: impl IntoIterator<Item = G::Node>,
+        mut goal: impl FnMut(&G::Node) -> bool,
+    ) -> Option<(G::Node, G::Cost)> {
+        let graph = self.graph;
+        let mut queue = VecDeque::new();
+        for start in starts {
+            let idx = graph.index(&start);
+            if self.costs[idx].is_none() {
+                self.costs[idx] = Some(G::Cost::default());
+                queue.push_back((start, G::Cost::default()))
+            }
+        }
+        while let Some((node, cost)) = queue.pop_front() {
+            if goal(&node) {
+                return Some((node, cost));
+            }
+            graph.neighbours(&node, |next, step| {
+                let idx = graph.index(&next);
+                if self.costs[idx].is_none() {
+                    self.costs[idx] = Some(cost + step);
+                    if let Some(came_from) = &mut self.came_from {
+                        came_from[idx] = Some(node.clone())
+                    }
+                    queue.push_back((next, cost + step))
+                }
+            });
+        }
+        None
+    }
+
+    /// Find the cheapest path from the starts, with their initial costs, to a goal
+    pub fn dijkstra(
+        &mut self,
+        starts: impl IntoIterator<Item = (G::Node, G::Cost)>,
+        goal: impl FnMut(&G::Node) -> bool,
+    ) -> Option<(G::Node, G::Cost)> {
+        self.a_star(starts, goal, |_| G::Cost::default())
+    }
+
+    /// Find the cheapest path from the starts, with their initial costs, to a goal
+    ///
+    /// `heuristic` must never be more than the cost still needed to reach a goal
+    pub fn a_star(
+        &mut self,
+        starts: impl IntoIterator<Item = (G::Node, G::Cost)>,
+        mut goal: impl FnMut(&G::Node) -> bool,
+        heuristic: impl Fn(&G::Node) -> G::Cost,
+    ) -> Option<(G::Node, G::Cost)> {
+        let graph = self.graph;
+        let mut queue = BinaryHeap::new();
+        for (start, cost) in starts {
+            let idx = graph.index(&start);
+            if !matches!(self.costs[idx], Some(old) if old <= cost) {
+                self.costs[idx] = Some(cost);
+                queue.push(Queued {
+                    priority: cost + heuristic(&start),
+                    cost,
+                    node: start,
+                })
+            }
+        }
+        while let Some(Queued { cost, node, .. }) = queue.pop() {
+            if self.costs[graph.index(&node)] != Some(cost) {
+                continue; // reached again since, with a lower cost
+            }
+            if goal(&node) {
+                return Some((node, cost));
+            }
+            graph.neighbours(&node, |next, step| {
+                let idx = graph.index(&next);
+                let cost = cost + step;
+                if !matches!(self.costs[idx], Some(old) if old <= cost) {
+                    self.costs[idx] = Some(cost);
+                    if let Some(came_from) = &mut self.came_from {
+                        came_from[idx] = Some(node.clone())
+                    }
+                    queue.push(Queued {
+                        priority: cost + heuristic(&next),
+                        cost,
+                        node: next,
+                    })
+                }
+            });
+        }
+        None
+    }
+}
+
+/// Lowest costs between all the pairs of nodes, from the costs of the direct moves
+///
+/// `costs[from][to]` is `None` where there is no move, and stays so if `to` is unreachable
+pub fn floyd_warshall<C: Cost>(costs: &mut [Vec<Option<C>>]) {
+    for (node, row) in costs.iter_mut().enumerate() {
+        row[node] = Some(C::default());
+    }
+    for k in 0..costs.len() {
+        let via_k = costs[k].clone();
+        for row in costs.iter_mut() {
+            let Some(to_k) = row[k] else {
+                continue;
+            };
+            for (cost, from_k) in row.iter_mut().zip(&via_k) {
+                if let Some(from_k) = *from_k {
+                    if !matches!(*cost, Some(old) if old <= to_k + from_k) {
+                        *cost = Some(to_k + from_k)
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A directed graph, with the moves from each node
+    struct Moves(Vec<Vec<(usize, u32)>>);
+    impl Graph for Moves {
+        type Node = usize;
+        type Cost = u32;
+
+        fn size(&self) -> usize {
+            self.0.len()
+        }
+        fn index(&self, &node: &usize) -> usize {
+            node
+        }
+        fn neighbours(&self, &node: &usize, mut edge: impl FnMut(usize, u32)) {
+            for &(next, cost) in self.0[node].iter() {
+                edge(next, cost)
+            }
+        }
+    }
+
+    /// Node 5 cannot be reached, and no node can be reached from 4 and 5
+    fn graph() -> Moves {
+        Moves(vec![
+            vec![(1, 4), (2, 1)],
+            vec![(3, 1)],
+            vec![(1, 2), (3, 5)],
+            vec![(4, 3)],
+            vec![],
+            vec![],
+        ])
+    }
+
+    /// The same graph, with all the moves costing 1
+    fn unit_graph() -> Moves {
+        let Moves(moves) = graph();
+        Moves(
+            moves
+                .into_iter()
+                .map(|moves| moves.into_iter().map(|(next, _)| (next, 1)).collect())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn bfs() {
+        let graph = unit_graph();
+        let mut search = Search::new(&graph).recording_paths();
+        assert_eq!(search.bfs([0], |&node| node == 4), Some((4, 3)));
+        assert_eq!(search.path(&4), vec![0, 1, 3, 4]);
+        assert_eq!(search.cost(&2), Some(1));
+
+        let mut search = Search::new(&graph);
+        assert_eq!(search.bfs([0], |&node| node == 5), None);
+        assert_eq!(search.cost(&4), Some(3));
+        assert_eq!(search.cost(&5), None);
+
+        // the goal can be a start
+        let mut search = Search::new(&graph);
+        assert_eq!(search.bfs([3, 0], |&node| node == 3), Some((3, 0)));
+    }
+
+    #[test]
+    fn dijkstra() {
+        let graph = graph();
+        let mut search = Search::new(&graph).recording_paths();
+        assert_eq!(search.dijkstra([(0, 0)], |&node| node == 4), Some((4, 7)));
+        assert_eq!(search.path(&4), vec![0, 2, 1, 3, 4]);
+        assert_eq!(search.cost(&1), Some(3));
+
+        let mut search = Search::new(&graph);
+        assert_eq!(search.dijkstra([(0, 0)], |&node| node == 5), None);
+        assert_eq!(search.cost(&5), None);
+
+        // the starts keep their initial costs
+        let mut search = Search::new(&graph).recording_paths();
+        assert_eq!(
+            search.dijkstra([(0, 10), (3, 2)], |&node| node == 4),
+            Some((4, 5))
+        );
+        assert_eq!(search.path(&4), vec![3, 4]);
+    }
+
+    #[test]
+    fn a_star() {
+        let graph = graph();
+        // the exact costs to 4
+        let remaining = [7, 4, 6, 3, 0, 0];
+        let mut search = Search::new(&graph).recording_paths();
+        assert_eq!(
+            search.a_star([(0, 0)], |&node| node == 4, |&node| remaining[node]),
+            Some((4, 7))
+        );
+        assert_eq!(search.path(&4), vec![0, 2, 1, 3, 4]);
+
+        let mut search = Search::new(&graph);
+        assert_eq!(search.a_star([(0, 0)], |&node| node == 5, |_| 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "The paths were not recorded")]
+    fn path_not_recorded() {
+        let graph = graph();
+        let mut search = Search::new(&graph);
+        search.bfs([0], |_| false);
+        search.path(&4);
+    }
+
+    #[test]
+    fn all_pairs() {
+        let Moves(moves) = graph();
+        let mut costs = vec![vec![None; moves.len()]; moves.len()];
+        for (from, moves) in moves.iter().enumerate() {
+            for &(to, cost) in moves {
+                costs[from][to] = Some(cost)
+            }
+        }
+        floyd_warshall(&mut costs);
+        assert_eq!(
+            costs[0],
+            vec![Some(0), Some(3), Some(1), Some(4), Some(7), None]
+        );
+        assert_eq!(costs[2][4], Some(6));
+        assert_eq!(costs[3][4], Some(3));
+        // unreachable pairs stay so
+        assert_eq!(costs[4], vec![None, None, None, None, Some(0), None]);
+        assert_eq!(costs[1][0], None);
+        assert_eq!(costs[0][5], None);
+        assert_eq!(costs[5][5], Some(0));
+    }
+}
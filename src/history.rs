@@ -0,0 +1,248 @@
+//! History of the timings of the parts, recorded at each run to spot the slowdowns
+
+use std::{
+    error::Error,
+    fmt::Display,
+    fs,
+    io::Write,
+    path::Path,
+    process,
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    bench::BenchStats,
+    inputs::read_file,
+    report::{format_duration, result_records, simple_table},
+    runner::{DayResult, RunResult},
+};
+
+/// Timing of a part in a past run
+#[derive(Debug, Clone)]
+pub struct HistoryRecord {
+    /// Seconds since the Unix epoch
+    timestamp: u64,
+    commit: String,
+    /// Enabled cargo features, comma separated
+    features: String,
+    day: usize,
+    part: usize,
+    /// Name of the input variant
+    input: String,
+    time: Duration,
+}
+
+/// Commit of the working directory, marked `-dirty` if it has uncommitted changes
+fn git_commit() -> String {
+    let git = |args: &[&str]| {
+        process::Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_owned())
+    };
+    match (
+        git(&["rev-parse", "--short", "HEAD"]),
+        git(&["status", "--porcelain"]),
+    ) {
+        (Some(commit), Some(changes)) if !changes.is_empty() => format!("{commit}-dirty"),
+        (Some(commit), _) => commit,
+        (None, _) => "unknown".to_owned(),
+    }
+}
+
+/// Whether the name of an input can be recorded, as the fields of the history are separated by
+/// whitespace
+pub fn recordable_input(input: &str) -> bool {
+    !input.is_empty() && !input.contains(char::is_whitespace)
+}
+
+/// Append the timings of the solved parts to the history file
+///
+/// Each line contains `timestamp commit features day part input time_ns`, with `-` for no features
+pub fn save_history(path: &Path, input: &str, results: &[DayResult]) -> Result<(), Box<dyn Error>> {
+    if !recordable_input(input) {
+        return Err(format!(
+            "The input {input:?} cannot be recorded in the history, as it is empty or has whitespace"
+        )
+        .into());
+    }
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)?
+        .as_secs();
+    let commit = git_commit();
+    let features = match crate::features().join(",") {
+        features if features.is_empty() => "-".to_owned(),
+        features => features,
+    };
+    let mut content = String::new();
+    if !path.exists() {
+        content.push_str("# timestamp commit features day part input time_ns\n")
+    }
+    for (day, part, res) in result_records(results) {
+        // cached results were timed in another run
+        if let Ok(res @ RunResult { cached: false, .. }) = res {
+            content.push_str(&format!(
+                "{timestamp} {commit} {features} {day} {part} {input} {}\n",
+                res.time.as_nanos()
+            ))
+        }
+    }
+    fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .map_err(|err| {
+            format!(
+                "Error while writing file {}: {}",
+                path.to_string_lossy(),
+                err
+            )
+            .into()
+        })
+}
+
+pub fn load_history(path: &Path) -> Result<Vec<HistoryRecord>, Box<dyn Error>> {
+    let mut records = vec![];
+    for line in read_file(path)?.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |err: &dyn Display| format!("Invalid history line {line:?}: {err}");
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [timestamp, commit, features, day, part, input, time_ns] = fields[..] else {
+            return Err(invalid(&"expected 7 fields").into());
+        };
+        records.push(HistoryRecord {
+            timestamp: timestamp.parse().map_err(|err| invalid(&err))?,
+            commit: commit.to_owned(),
+            features: features.to_owned(),
+            day: day.parse().map_err(|err| invalid(&err))?,
+            part: part.parse().map_err(|err| invalid(&err))?,
+            input: input.to_owned(),
+            time: Duration::from_nanos(time_ns.parse().map_err(|err| invalid(&err))?),
+        })
+    }
+    Ok(records)
+}
+
+/// Identifies the runs that can be compared, as (day, part, input, features)
+type SeriesKey<'a> = (usize, usize, &'a str, &'a str);
+
+/// Show the trend of each part, comparing its last run with the median of the `window` previous ones
+///
+/// Runs with different inputs or features are kept apart. Also gives the parts slower than
+/// their median by more than `threshold` percent
+pub fn history_report(
+    records: &[HistoryRecord],
+    window: usize,
+    threshold: f64,
+) -> (String, Vec<String>) {
+    // runs of each (day, part, input, features)
+    let mut series: Vec<(SeriesKey, Vec<&HistoryRecord>)> = vec![];
+    for record in records {
+        let key = (
+            record.day,
+            record.part,
+            record.input.as_str(),
+            record.features.as_str(),
+        );
+        match series.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, runs)) => runs.push(record),
+            None => series.push((key, vec![record])),
+        }
+    }
+    series.sort_by_key(|(key, _)| *key);
+
+    let mut regressions = vec![];
+    let rows: Vec<Vec<String>> = series
+        .iter_mut()
+        .map(|((day, part, input, features), runs)| {
+            runs.sort_by_key(|r| r.timestamp);
+            let (last, previous) = runs.split_last().unwrap();
+            let previous: Vec<Duration> = previous
+                .iter()
+                .rev()
+                .take(window)
+                .map(|r| r.time)
+                .collect();
+            let (median, change) = if previous.is_empty() {
+                ("-".to_owned(), "-".to_owned())
+            } else {
+                let median = BenchStats::from_samples(previous).median;
+                let change = (last.time.as_secs_f64() / median.as_secs_f64() - 1.) * 100.;
+                if change > threshold {
+                    regressions.push(format!(
+                        "Day {day} part {part} ({input}) got slower: {} against a median of {} ({change:+.1}%)",
+                        format_duration(last.time),
+                        format_duration(median)
+                    ))
+                }
+                (format_duration(median), format!("{change:+.1}%"))
+            };
+            vec![
+                day.to_string(),
+                part.to_string(),
+                input.to_string(),
+                features.to_string(),
+                runs.len().to_string(),
+                format_duration(runs.iter().map(|r| r.time).min().unwrap()),
+                median,
+                format_duration(last.time),
+                change,
+                last.commit.clone(),
+            ]
+        })
+        .collect();
+    let header = [
+        "day", "part", "input", "features", "runs", "best", "median", "last", "change", "commit",
+    ];
+    (simple_table(&header, &rows), regressions)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+    use crate::{memory::MemoryUsage, PuzzleResult};
+
+    #[test]
+    fn saved_inputs() {
+        let part = |ns| {
+            Some(Ok(RunResult {
+                res: PuzzleResult::Numeric(42),
+                time: Duration::from_nanos(ns),
+                memory: MemoryUsage::default(),
+                cached: false,
+                changed: false,
+                check: None,
+            }))
+        };
+        let results = vec![DayResult {
+            parse_time: None,
+            parts: vec![part(1000), part(2000)],
+        }];
+        let path = env::temp_dir().join(format!("year2022-history-{}", process::id()));
+        let saved = save_history(&path, "big_input", &results);
+        // a name with a space would be read as two fields
+        let spaced = save_history(&path, "big input", &results);
+        let records = load_history(&path);
+        fs::remove_file(&path).unwrap();
+
+        saved.unwrap();
+        assert!(spaced.is_err());
+        let records = records.unwrap();
+        let loaded: Vec<_> = records
+            .iter()
+            .map(|r| (r.day, r.part, r.input.as_str(), r.time.as_nanos()))
+            .collect();
+        assert_eq!(
+            loaded,
+            [(1, 1, "big_input", 1000), (1, 2, "big_input", 2000)]
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Inputs of the days, found in the inputs dir with their parameters and answers
+//!
+//! Each day has a directory named after its number, holding its input files and the files related
+//! to them (see [`get_related_path`])
+
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    runner::{selected_parts, PartSpec},
+    Day, Params,
+};
+
+/// A day to run
+#[derive(Debug, Clone)]
+pub struct DayInput {
+    pub parts: Vec<usize>,
+    /// File the input was read from, `None` for stdin
+    pub path: Option<PathBuf>,
+    /// Content of the input and parameters, or the error met loading them
+    pub loaded: Result<(String, Params), String>,
+}
+impl DayInput {
+    /// Load the input of a day, with its parameters
+    pub fn load(
+        day: &dyn Day,
+        parts: Vec<usize>,
+        path: Option<PathBuf>,
+        overrides: &[(String, String)],
+    ) -> Self {
+        let content = match &path {
+            Some(path) => read_file(path),
+            None => read_stdin(),
+        };
+        let loaded = content
+            .and_then(|content| Ok((content, load_params(day, path.as_deref(), overrides)?)))
+            .map_err(|err| err.to_string());
+        Self {
+            parts,
+            path,
+            loaded,
+        }
+    }
+}
+
+pub fn get_input_from_input_dir(day: u8, dir: &Path, name: &str) -> PathBuf {
+    let mut dir = dir.to_path_buf();
+    dir.push(day.to_string());
+    dir.push(name);
+    dir
+}
+
+/// Find a variant of the input of a day, failing with the list of the existing ones
+pub fn get_input_variant(day: u8, dir: &Path, name: &str) -> Result<PathBuf, String> {
+    let input = get_input_from_input_dir(day, dir, name);
+    if input.is_file() {
+        return Ok(input);
+    }
+    let variants = input_variants(day, dir);
+    Err(if variants.is_empty() {
+        format!("Day {day} has no inputs")
+    } else {
+        format!(
+            "Day {day} has no input {name:?}, available variants: {}",
+            variants.join(", ")
+        )
+    })
+}
+
+/// Names of the input files of a day, skipping their related files
+fn input_variants(day: u8, dir: &Path) -> Vec<String> {
+    let is_related = |name: &str| {
+        let kind = name.rsplit_once('_').map_or(name, |(_, kind)| kind);
+        kind == "params"
+            || kind
+                .strip_prefix("answer")
+                .is_some_and(|part| part.parse::<usize>().is_ok())
+    };
+    let Ok(entries) = fs::read_dir(dir.join(day.to_string())) else {
+        return vec![];
+    };
+    let mut variants: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| !is_related(name))
+        .collect();
+    variants.sort();
+    variants
+}
+
+/// Name of the input files of the examples
+pub const EXAMPLE_INPUT: &str = "test_input";
+
+/// Find which of the requested parts of the example of a day can be checked
+///
+/// Only the parts with a known answer are run, as solutions could be
+/// specialized on the shape of the real input
+pub fn example_parts(
+    day: &dyn Day,
+    dir: &Path,
+    parts: PartSpec,
+) -> Result<(Vec<usize>, PathBuf), String> {
+    let number = day.number();
+    let input = get_input_from_input_dir(number, dir, EXAMPLE_INPUT);
+    if !input.exists() {
+        return Err(format!("Day {number} has no example"));
+    }
+    let parts: Vec<usize> = selected_parts(day, parts)
+        .map_err(|err| err.to_string())?
+        .into_iter()
+        .filter(|part| get_related_path(&input, &format!("answer{part}")).exists())
+        .collect();
+    if parts.is_empty() {
+        return Err(format!("Day {number} has no known example answers"));
+    }
+    Ok((parts, input))
+}
+
+/// Path of a file related to a given input (answers, parameters)
+///
+/// The `<kind>` file for `input` is `<kind>`, for any other input file
+/// `<name>` it is `<name>_<kind>`. E.g. the answers for `test_input` are in
+/// `test_input_answer1` and `test_input_answer2`
+pub fn get_related_path(input: &Path, kind: &str) -> PathBuf {
+    let file_name = match input.file_name() {
+        Some(name) if name != "input" => format!("{}_{kind}", name.to_string_lossy()),
+        _ => kind.to_owned(),
+    };
+    input.with_file_name(file_name)
+}
+
+/// Parse a parameter given as `name=value`
+pub fn parse_param(s: &str) -> Result<(String, String), String> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Parameter {s:?} is not in the form name=value"))?;
+    Ok((name.trim().to_owned(), value.trim().to_owned()))
+}
+
+/// Load the parameters for an input, overriding them with the given ones
+///
+/// Parameters are read from the `params` file related to the input file, if it exists,
+/// containing a `name=value` pair on each line. Only the overrides the day declares are used
+pub fn load_params(
+    day: &dyn Day,
+    input: Option<&Path>,
+    overrides: &[(String, String)],
+) -> Result<Params, Box<dyn Error>> {
+    let declared = |name: &str| day.params().iter().any(|p| p.name == name);
+    let mut params = Params::default();
+    let path = input.map(|input| get_related_path(input, "params"));
+    if let Some(path) = path.filter(|path| path.exists()) {
+        for line in read_file(&path)?.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = parse_param(line)?;
+            if !declared(&name) {
+                return Err(format!(
+                    "Unknown parameter {name} for day {} in {}",
+                    day.number(),
+                    path.to_string_lossy()
+                )
+                .into());
+            }
+            params.set(name, value)
+        }
+    }
+    for (name, value) in overrides {
+        if declared(name) {
+            params.set(name.clone(), value.clone())
+        }
+    }
+    Ok(params)
+}
+
+pub fn read_file(path: &Path) -> Result<String, Box<dyn Error>> {
+    fs::read_to_string(path).map_err(|err| {
+        format!(
+            "Error while accessing file {}: {}",
+            path.to_string_lossy(),
+            err.to_string()
+        )
+        .into()
+    })
+}
+
+fn read_stdin() -> Result<String, Box<dyn Error>> {
+    let mut content = String::new();
+    io::stdin()
+        .read_to_string(&mut content)
+        .map_err(|err| format!("Error while reading stdin: {err}"))?;
+    Ok(content)
+}
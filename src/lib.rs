@@ -0,0 +1,78 @@
+//! Solutions of the Advent of Code 2022 puzzles
+//!
+//! The days are found with [`day`] or listed in [`DAYS`], and [`solve`] runs a part of a day
+//! on an input.
+//!
+//! The other modules hold the runner of the `year2022` executable: solving the parts and
+//! checking their answers, loading the inputs, reporting the results and talking to the
+//! Advent of Code site. They are public only for the executable and its tests, and are not part
+//! of the documented API.
+#![feature(is_some_and)]
+#![feature(slice_group_by)]
+#![feature(int_roundings)]
+
+extern crate regex;
+#[macro_use]
+extern crate lazy_static;
+extern crate arrayvec;
+extern crate termion;
+
+use std::error::Error;
+
+#[doc(hidden)]
+pub mod aoc;
+#[doc(hidden)]
+pub mod bench;
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod dashboard;
+mod days;
+#[doc(hidden)]
+pub mod history;
+#[doc(hidden)]
+pub mod inputs;
+#[doc(hidden)]
+pub mod memory;
+#[doc(hidden)]
+pub mod report;
+#[doc(hidden)]
+pub mod runner;
+
+/// The graph searches the days are built on
+#[doc(hidden)]
+pub use days::search;
+pub use days::{Day, Param, Params, ParseError, Parsed, PuzzleResult, ReprFn, ResultRepr, DAYS};
+
+/// Find the solution of a day
+pub fn day(number: u8) -> Option<&'static dyn Day> {
+    days::get(number)
+}
+
+/// The cargo features enabled in this build that change the solutions
+pub(crate) fn features() -> Vec<&'static str> {
+    let mut features = vec![];
+    if cfg!(feature = "_16_bitmaps") {
+        features.push("_16_bitmaps")
+    }
+    if cfg!(feature = "_19_trace") {
+        features.push("_19_trace")
+    }
+    features
+}
+
+/// Solve a part (starting from 1) of a day, with the parameters of the real puzzle
+pub fn solve(day: u8, part: usize, input: &str) -> Result<PuzzleResult, Box<dyn Error>> {
+    solve_with_params(day, part, input, &Params::default())
+}
+
+/// Solve a part (starting from 1) of a day, overriding some of its parameters
+pub fn solve_with_params(
+    day: u8,
+    part: usize,
+    input: &str,
+    params: &Params,
+) -> Result<PuzzleResult, Box<dyn Error>> {
+    let solution = self::day(day).ok_or_else(|| format!("Day {day} is unsolved"))?;
+    solution.parse(input, params)?.solve(part)
+}
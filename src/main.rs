@@ -1,43 +1,65 @@
 #![feature(is_some_and)]
-#![feature(slice_group_by)]
-#![feature(int_roundings)]
 
 extern crate clap;
 extern crate regex;
-#[macro_use]
-extern crate lazy_static;
-extern crate arrayvec;
 
-use clap::{builder::PossibleValue, Parser, ValueEnum};
-use days::{PuzzleResult, SolveFn, DAYS};
+use clap::{builder::PossibleValue, Parser, Subcommand, ValueEnum};
 use regex::Regex;
-use std::{
-    error::Error,
-    ffi::OsString,
-    fs,
-    path::{Path, PathBuf},
-    time::{Duration, Instant},
+use std::{error::Error, ffi::OsString, path::PathBuf, time::Duration};
+use year2022::{
+    aoc::Site,
+    bench::{bench_days, load_baseline, save_baseline},
+    cache::Cache,
+    history::{history_report, load_history, recordable_input, save_history},
+    inputs::{
+        example_parts, get_input_from_input_dir, get_input_variant, parse_param, DayInput,
+        EXAMPLE_INPUT,
+    },
+    memory::CountingAllocator,
+    report::{
+        bench_table, days_list, result_csv, result_html, result_json, result_markdown,
+        result_records, result_table,
+    },
+    runner::{
+        check_results, run_visualizations, selected_parts, solve_in_worker, DayResult, PartSpec,
+        Solver, Watcher,
+    },
 };
 
-mod days;
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// A selection of days, such as `1-5,12,20-25`
+#[derive(Debug, Clone)]
+struct DaySelection(Vec<u8>);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum PartSpec {
-    First,
-    Second,
-    Both,
+enum OutputFormat {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+    Html,
 }
 
-impl ValueEnum for PartSpec {
+impl ValueEnum for OutputFormat {
     fn value_variants<'a>() -> &'a [Self] {
-        &[Self::First, Self::Second, Self::Both]
+        &[
+            Self::Table,
+            Self::Json,
+            Self::Csv,
+            Self::Markdown,
+            Self::Html,
+        ]
     }
 
     fn to_possible_value(&self) -> Option<PossibleValue> {
         Some(match self {
-            PartSpec::First => PossibleValue::new("first").alias("1"),
-            PartSpec::Second => PossibleValue::new("second").alias("2"),
-            PartSpec::Both => PossibleValue::new("both"),
+            OutputFormat::Table => PossibleValue::new("table"),
+            OutputFormat::Json => PossibleValue::new("json"),
+            OutputFormat::Csv => PossibleValue::new("csv"),
+            OutputFormat::Markdown => PossibleValue::new("markdown").alias("md"),
+            OutputFormat::Html => PossibleValue::new("html"),
         })
     }
 }
@@ -46,395 +68,530 @@ impl ValueEnum for PartSpec {
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Inputs directory
     #[arg(long)]
     inputs_dir: Option<PathBuf>,
 
-    /// Alternative input file name
+    /// Alternative input file name, or `-` to read it from stdin
     #[arg(long)]
     input: Option<OsString>,
 
-    /// Day to solve (1-25) [default all]
-    #[arg(long, short)]
-    day: Option<u8>,
+    /// Name of the input file to use in the inputs dir of each day
+    #[arg(long, default_value = "input")]
+    variant: String,
 
-    /// Part to solve
-    #[arg(short, long, default_value = "both")]
+    /// Days to solve, as a list of days and ranges (e.g. `1-5,12,20-25`) [default all]
+    #[arg(long, short, value_parser = parse_days)]
+    day: Option<DaySelection>,
+
+    /// Part to solve of each day: `all`, `first`, `second` or its number
+    #[arg(short, long, default_value = "all", value_parser = parse_part)]
     part: PartSpec,
 
-    /// Run visualizations
+    /// Format of the results
+    #[arg(short, long, visible_alias = "format", default_value = "table")]
+    output: OutputFormat,
+
+    /// Run visualizations whose name (`day<d>/<name>`) matches
     #[arg(short, long)]
     visualize: Vec<Regex>,
+
+    /// Directory where the visualizations are written
+    #[arg(long, default_value = "visualizations")]
+    visualize_dir: PathBuf,
+
+    /// Check the results against the answer files
+    #[arg(long)]
+    check: bool,
+
+    /// Set a puzzle parameter, as `name=value`
+    #[arg(long, value_parser = parse_param)]
+    param: Vec<(String, String)>,
+
+    /// Run the examples (`test_input`) and check their answers
+    #[arg(long)]
+    example: bool,
+
+    /// Number of parts to run in parallel
+    #[arg(short, long, default_value = "1")]
+    jobs: usize,
+
+    /// Benchmark each part, running it this many times
+    #[arg(long)]
+    bench: Option<usize>,
+
+    /// Benchmark each part, running it for this time (e.g. `500ms`, `10s`, `1m`)
+    #[arg(long, value_parser = parse_duration)]
+    bench_time: Option<Duration>,
+
+    /// Save the benchmark results as a baseline
+    #[arg(long)]
+    save_baseline: Option<PathBuf>,
+
+    /// Compare the benchmark results with a saved baseline
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// List the solved days, with their parameters and visualizations
+    #[arg(long)]
+    list: bool,
+
+    /// Run each part in its own process, killing it after this time (e.g. `500ms`, `10s`, `1m`)
+    #[arg(long, value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Directory of the result cache, used only if it exists or `--cache` is given
+    #[arg(long, default_value = ".aoc-cache")]
+    cache_dir: PathBuf,
+
+    /// Use the result cache, creating its dir if needed
+    #[arg(long)]
+    cache: bool,
+
+    /// Do not use the result cache
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Solve again the parts with a cached result, updating the cache
+    #[arg(long)]
+    refresh: bool,
+
+    /// Keep running, solving again the days whose input, parameters or answers change
+    #[arg(long)]
+    watch: bool,
+
+    /// Show a live dashboard of the run, if the output is a terminal
+    #[arg(long)]
+    tui: bool,
+
+    /// Address of the Advent of Code site
+    #[arg(long, default_value = "https://adventofcode.com")]
+    base_url: String,
+
+    /// File with the session cookie for the Advent of Code site, if `AOC_SESSION` is not set
+    #[arg(long, default_value = ".aoc-session")]
+    session_file: PathBuf,
+
+    /// File where the timings of the runs are recorded
+    #[arg(long, default_value = ".aoc-history")]
+    history_file: PathBuf,
+
+    /// Do not record the timings of this run
+    #[arg(long)]
+    no_history: bool,
+
+    /// Run a single part on the input from stdin, writing its outcome for the parent process
+    #[arg(long, hide = true)]
+    worker: bool,
 }
 
-#[derive(Debug, Clone)]
-struct RunResult {
-    res: PuzzleResult,
-    time: Duration,
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show the recorded timings of each part, flagging the ones that got slower
+    History {
+        /// Number of previous runs the last one is compared with
+        #[arg(long, default_value = "5")]
+        window: usize,
+
+        /// Slowdown over the median of the previous runs that is flagged, in percent
+        #[arg(long, default_value = "10")]
+        threshold: f64,
+    },
+    /// Download the missing inputs of the selected days into the inputs dir
+    ///
+    /// The downloads are made with `curl`, which must be installed and in the `PATH`
+    Fetch {
+        /// Least time between two downloads (e.g. `500ms`, `10s`, `1m`)
+        #[arg(long, default_value = "3s", value_parser = parse_duration)]
+        delay: Duration,
+    },
+    /// Solve a part on the real input and submit the answer to the Advent of Code site
+    Submit {
+        /// Day to submit
+        #[arg(long, short)]
+        day: u8,
+
+        /// Part to submit
+        #[arg(long, short)]
+        part: usize,
+
+        /// File where the submissions are recorded
+        #[arg(long, default_value = ".aoc-submissions")]
+        log: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone, Copy)]
 struct RunSetup {}
 
-#[derive(Debug, Clone)]
-struct DayResult {
-    p1: Option<RunResult>,
-    p2: Option<RunResult>,
+/// Parse a selection of days, as a comma separated list of days and ranges
+fn parse_days(s: &str) -> Result<DaySelection, String> {
+    let parse_day = |day: &str| -> Result<u8, String> {
+        match day.trim().parse() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ => Err(format!("Invalid day {day:?}, days are between 1 and 25")),
+        }
+    };
+    let mut days = vec![];
+    for item in s.split(',') {
+        match item.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(format!("Invalid range {item:?}"));
+                }
+                days.extend(first..=last)
+            }
+            None => days.push(parse_day(item)?),
+        }
+    }
+    days.sort();
+    days.dedup();
+    Ok(DaySelection(days))
 }
 
-fn run_part(part: SolveFn, input: &str) -> Result<RunResult, Box<dyn Error>> {
-    let start = Instant::now();
-    let res = part(input);
-    let time = start.elapsed();
-    Ok(RunResult { res: res?, time })
+/// Parse a selection of parts: `all` (or `both`), `first`, `second` or the number of a part
+fn parse_part(s: &str) -> Result<PartSpec, String> {
+    match s.trim() {
+        "all" | "both" => Ok(PartSpec::All),
+        "first" => Ok(PartSpec::Only(1)),
+        "second" => Ok(PartSpec::Only(2)),
+        part => match part.parse() {
+            Ok(part @ 1..) => Ok(PartSpec::Only(part)),
+            _ => Err(format!("Invalid part {s:?}")),
+        },
+    }
 }
 
-fn run_day(day: u8, parts: PartSpec, input: &str) -> Result<DayResult, Box<dyn Error>> {
-    use PartSpec::*;
-    match (parts, &DAYS[day as usize - 1]) {
-        (First, (Some(p1), _, _)) => Ok(DayResult {
-            p1: Some(run_part(*p1, input)?),
-            p2: None,
-        }),
-        (Second, (_, Some(p2), _)) => Ok(DayResult {
-            p1: None,
-            p2: Some(run_part(*p2, input)?),
-        }),
-        (Both, (Some(p1), Some(p2), _)) => Ok(DayResult {
-            p1: Some(run_part(*p1, input)?),
-            p2: Some(run_part(*p2, input)?),
-        }),
-        // Errors
-        (First, (None, _, _)) => Err(format!("First part of day {day} is unsolved").into()),
-        (Second, (_, None, _)) => Err(format!("Second part of day {day} is unsolved").into()),
-        (Both, (None, _, _) | (_, None, _)) => {
-            Err(format!("Some parts of day {day} are unsolved").into())
-        }
-    }
+/// Parse a duration given as `<number><unit>`, with unit one of `ns`, `us`, `ms`, `s`, `m`
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+        .unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("Invalid duration {s:?}"))?;
+    let unit_secs = match unit.trim() {
+        "ns" => 1e-9,
+        "us" => 1e-6,
+        "ms" => 1e-3,
+        "s" | "" => 1.,
+        "m" => 60.,
+        unit => return Err(format!("Unknown time unit {unit:?}")),
+    };
+    Ok(Duration::from_secs_f64(value * unit_secs))
 }
 
 fn parse_args() -> Result<Args, Box<dyn Error>> {
     let args = Args::parse();
+    if args.list || args.command.is_some() {
+        // nothing will be run
+        return Ok(args);
+    }
     // checking only one file is given
     if !(args.inputs_dir.is_some() != args.input.is_some()) {
         return Err("Only one between inputs dir and alternative input can be given".into());
     }
-    // checking all info are given
-    match args.day {
-        Some(day) => {
-            if !(1 <= day && day <= 25) {
-                return Err("Day should be between 1 and 25".into());
-            }
-        }
-        None => {
-            if let Some(_) = args.input {
-                return Err("Cannot given alternate input when running all days".into());
-            }
-            if args.part != PartSpec::Both {
-                return Err("Cannot run single parts of all days".into());
-            }
-        }
+    if args.jobs == 0 {
+        return Err("At least one job is needed".into());
+    }
+    if args.bench == Some(0) {
+        return Err("Benchmarks need at least one run".into());
+    }
+    if (args.save_baseline.is_some() || args.baseline.is_some())
+        && args.bench.is_none()
+        && args.bench_time.is_none()
+    {
+        return Err("Baselines can be used only when benchmarking".into());
+    }
+    if args.example && args.input.is_some() {
+        return Err("Examples are taken from the inputs dir".into());
+    }
+    if args.example && args.variant != "input" {
+        return Err(format!("Examples always use the {EXAMPLE_INPUT} variant").into());
+    }
+    if args.input.is_some() && args.variant != "input" {
+        return Err("Variants are taken from the inputs dir".into());
+    }
+    if !args.no_history && !recordable_input(&args.variant) {
+        return Err(format!(
+            "The variant {:?} cannot be recorded in the history, as it is empty or has whitespace: \
+             rename it or give --no-history",
+            args.variant
+        )
+        .into());
+    }
+    if args.input.is_some() && args.day.as_ref().map_or(true, |days| days.0.len() != 1) {
+        return Err("An alternative input can be given only when running a single day".into());
+    }
+    if args.timeout.is_some() && (args.bench.is_some() || args.bench_time.is_some()) {
+        return Err("Benchmarks cannot be run with a timeout".into());
+    }
+    if args.watch && args.input.as_ref().is_some_and(|input| input == "-") {
+        return Err("The input from stdin cannot be watched".into());
+    }
+    if args.watch && (args.bench.is_some() || args.bench_time.is_some()) {
+        return Err("Benchmarks cannot be watched".into());
+    }
+    if args.cache && args.no_cache {
+        return Err("Only one between cache and no cache can be given".into());
+    }
+    if args.no_cache && args.refresh {
+        return Err("The cache cannot be refreshed when it is not used".into());
+    }
+    if args.worker && !(args.input.is_some() && matches!(args.part, PartSpec::Only(_))) {
+        return Err("Workers run a single part of a single input".into());
     }
     Ok(args)
 }
 
-fn get_input_from_input_dir(day: u8, dir: &Path) -> PathBuf {
-    let mut dir = dir.to_path_buf();
-    dir.push(day.to_string());
-    dir.push("input");
-    dir
-}
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = parse_args()?;
 
-fn read_file(path: &Path) -> Result<String, Box<dyn Error>> {
-    fs::read_to_string(path).map_err(|err| {
-        format!(
-            "Error while accessing file {}: {}",
-            path.to_string_lossy(),
-            err.to_string()
-        )
-        .into()
-    })
-}
+    if args.list {
+        print!("{}", days_list());
+        return Ok(());
+    }
 
-/// Center a string
-fn pad_center(s: &str, len: usize) -> String {
-    if s.len() >= len {
-        String::from(s)
-    } else {
-        let pad = (len - s.len()) / 2;
-        let mut res = String::with_capacity(len);
-        for _ in 0..pad {
-            res.push(' ')
+    let site = Site {
+        base_url: args.base_url.clone(),
+        session_file: args.session_file.clone(),
+    };
+    match &args.command {
+        Some(Command::History { window, threshold }) => {
+            let records = load_history(&args.history_file)?;
+            let (report, regressions) = history_report(&records, *window, *threshold);
+            print!("{report}");
+            for regression in regressions.iter() {
+                eprintln!("{regression}")
+            }
+            if !regressions.is_empty() {
+                return Err(format!("{} parts got slower", regressions.len()).into());
+            }
+            return Ok(());
         }
-        res.push_str(s);
-        while res.len() < len {
-            res.push(' ')
+        Some(Command::Fetch { delay }) => {
+            let dir = args
+                .inputs_dir
+                .as_ref()
+                .ok_or("The inputs dir is needed to fetch the inputs")?;
+            let days = match &args.day {
+                Some(DaySelection(days)) => days.clone(),
+                None => (1..=25).collect(),
+            };
+            return site.fetch_inputs(dir, &days, *delay);
         }
-        res
+        Some(Command::Submit { day, part, log }) => {
+            let dir = args
+                .inputs_dir
+                .as_ref()
+                .ok_or("The inputs dir is needed to submit the answers")?;
+            return site.submit_answer(dir, *day, *part, &args.param, log);
+        }
+        None => (),
     }
-}
-/// left pad a string
-fn pad_left(s: &str, len: usize) -> String {
-    if s.len() >= len {
-        String::from(s)
-    } else {
-        let pad = len - s.len();
-        let mut res = String::with_capacity(len);
-        for _ in 0..pad {
-            res.push(' ')
+
+    let to_run = days_to_run(&args)?;
+
+    // parameters not read by any day are likely typos
+    for (name, _) in args.param.iter() {
+        let declared = (1..=25)
+            .filter(|&d| to_run[d as usize - 1].is_some())
+            .filter_map(year2022::day)
+            .any(|day| day.params().iter().any(|p| p.name == name));
+        if !declared {
+            return Err(format!("No day to run has a parameter {name}").into());
         }
-        res.push_str(s);
-        res
     }
-}
-///create a string with a repeated char
-fn repeat_char(ch: char, len: usize) -> String {
-    let mut s = String::with_capacity(ch.len_utf8() * len);
-    for _ in 0..len {
-        s.push(ch)
+
+    if args.worker {
+        let Some(run) = to_run.iter().flatten().next() else {
+            return Err("Nothing to run".into());
+        };
+        let day = year2022::day(args.day.as_ref().unwrap().0[0]).unwrap();
+        print!("{}", solve_in_worker(day, run)?);
+        return Ok(());
     }
-    s
-}
 
-/// Format a duration
-fn format_duration(d: Duration) -> String {
-    // humantime::format_duration(d).to_string()
-    if d.as_secs() > 60 {
-        // writing it as {}m {}s
-        format!("{}m {:2}s", d.as_secs() / 60, d.as_secs() % 60)
-    } else if d.as_secs() > 0 {
-        // writing it as {}s {}ms
-        format!("{}s {:3}ms", d.as_secs(), d.subsec_millis())
-    } else if d.as_millis() > 0 {
-        // writing it as {}ms {}us
-        format!("{}ms {:3}us", d.as_millis(), d.subsec_micros() % 1000)
-    } else if d.as_micros() > 0 {
-        // writing it as {}us {}ns
-        format!("{}us {:3}ns", d.as_micros(), d.subsec_nanos() % 1000)
-    } else {
-        // writing it as {}ns
-        format!("{}ns", d.as_nanos())
+    if args.bench.is_some() || args.bench_time.is_some() {
+        let stats = bench_days(&to_run, args.bench, args.bench_time)?;
+        let baseline = args.baseline.as_deref().map(load_baseline).transpose()?;
+        print!("{}", bench_table(&stats, baseline.as_deref()));
+        if let Some(path) = &args.save_baseline {
+            save_baseline(path, &stats)?;
+        }
+        return Ok(());
     }
-}
 
-fn result_table(results: Vec<DayResult>) -> String {
-    let header = ("day", "part one", "part two");
-    let mut table = Vec::with_capacity(results.len());
-    let mut multilines = vec![];
-
-    let mut part_entry = |day: usize, part: usize, res: &RunResult| -> (String, String) {
-        (
-            match res.res.repr() {
-                days::ResultRepr::Short(s) => s,
-                days::ResultRepr::Multiline(s) => {
-                    multilines.push((day, part, s));
-                    "<multiline>".to_owned()
-                }
-            },
-            format_duration(res.time).to_string(),
-        )
+    let solver = Solver {
+        jobs: args.jobs,
+        timeout: args.timeout,
+        cache: if args.no_cache {
+            None
+        } else {
+            Cache::open(&args.cache_dir, args.cache)?
+        },
+        refresh: args.refresh,
+        tui: args.tui,
     };
 
-    for (d, res) in results.iter().enumerate() {
-        // erase empty lines
-        if res.p1.is_some() || res.p2.is_some() {
-            table.push((
-                (d + 1).to_string(),
-                res.p1.as_ref().map(|v| part_entry(d + 1, 1, v)),
-                res.p2.as_ref().map(|v| part_entry(d + 1, 2, v)),
-            ))
+    if args.watch {
+        // solving again the days whose files change, until killed
+        let mut watcher = Watcher::new(&to_run);
+        let mut to_rerun = to_run;
+        loop {
+            let (mut results, wall_time) = solver.solve(&to_rerun)?;
+            let failed_checks = if args.check || args.example {
+                check_results(&mut results, &to_rerun)?
+            } else {
+                vec![]
+            };
+            record_history(&args, &results)?;
+            let results = watcher.update(&to_rerun, results);
+            if let Err(err) = report(&args, &to_rerun, results, wall_time, &failed_checks) {
+                eprintln!("Error: {err}")
+            }
+            eprintln!("Watching for changes...");
+            to_rerun = watcher.wait_changes(&args.param);
         }
     }
 
-    // measuring column sizes
-    let day_col_size = {
-        let mut size = header.0.len();
-        for (s, _, _) in table.iter() {
-            size = size.max(s.len())
-        }
-        size
+    let (mut results, wall_time) = solver.solve(&to_run)?;
+    let failed_checks = if args.check || args.example {
+        check_results(&mut results, &to_run)?
+    } else {
+        vec![]
     };
-    let part1_res_col_size = {
-        let mut size = 0;
-        for (_, p, _) in table.iter() {
-            if let Some((s, _)) = p {
-                size = size.max(s.len())
-            }
-        }
-        size
+    record_history(&args, &results)?;
+    report(&args, &to_run, &results, wall_time, &failed_checks)
+}
+
+/// Decide what to run of each day, loading its input
+fn days_to_run(args: &Args) -> Result<Vec<Option<DayInput>>, Box<dyn Error>> {
+    let mut to_run = vec![None; 25];
+    let selection = match &args.day {
+        Some(DaySelection(days)) => days.clone(),
+        None => (1..=25).collect(),
     };
-    let part1_time_col_size = {
-        let mut size = 0;
-        for (_, p, _) in table.iter() {
-            if let Some((_, s)) = p {
-                size = size.max(s.len())
+    // a single day must be runnable, from a larger selection the others are skipped
+    let single = selection.len() == 1;
+    for d in selection {
+        let Some(day) = year2022::day(d) else {
+            if single {
+                return Err(format!("Day {d} is unsolved").into());
             }
-        }
-        size
-    };
-    let part2_res_col_size = {
-        let mut size = 0;
-        for (_, _, p) in table.iter() {
-            if let Some((s, _)) = p {
-                size = size.max(s.len())
+            continue;
+        };
+        let selected = if args.example && !single {
+            // only the days with a sample are run
+            example_parts(day, args.inputs_dir.as_ref().unwrap(), args.part)
+                .map(|(parts, input)| (parts, Some(input), Ok(())))
+        } else {
+            selected_parts(day, args.part)
+                .map_err(|err| err.to_string())
+                .map(|parts| match &args.input {
+                    Some(stdin) if stdin == "-" => (parts, None, Ok(())),
+                    Some(alternate_file) => (parts, Some(PathBuf::from(alternate_file)), Ok(())),
+                    None => {
+                        let dir = args.inputs_dir.as_ref().unwrap();
+                        let name = if args.example {
+                            EXAMPLE_INPUT
+                        } else {
+                            &args.variant
+                        };
+                        // a missing input fails the day, and is watched for
+                        let found = get_input_variant(d, dir, name).map(|_| ());
+                        (parts, Some(get_input_from_input_dir(d, dir, name)), found)
+                    }
+                })
+        };
+        match selected {
+            Ok((parts, input, Ok(()))) => {
+                to_run[d as usize - 1] = Some(DayInput::load(day, parts, input, &args.param))
             }
-        }
-        size
-    };
-    let part2_time_col_size = {
-        let mut size = 0;
-        for (_, _, p) in table.iter() {
-            if let Some((_, s)) = p {
-                size = size.max(s.len())
+            Ok((parts, path, Err(msg))) => {
+                to_run[d as usize - 1] = Some(DayInput {
+                    parts,
+                    path,
+                    loaded: Err(msg),
+                })
             }
+            Err(msg) if single => return Err(msg.into()),
+            Err(msg) => eprintln!("{msg}"),
         }
-        size
-    };
-
-    // measuring complete column size
-    let day_col_size = day_col_size + 2;
-    let part1_col_size =
-        part1_res_col_size + " (time: ".len() + part1_time_col_size + ")".len() + 2;
-    let part2_col_size =
-        part2_res_col_size + " (time: ".len() + part2_time_col_size + ")".len() + 2;
-
-    // preparing header
-    let hline = [
-        "+",
-        &repeat_char('-', day_col_size),
-        "+",
-        &repeat_char('-', part1_col_size),
-        "+",
-        &repeat_char('-', part2_col_size),
-        "+\n",
-    ]
-    .concat();
-
-    // build the table
-    let mut table_str = String::new();
-    table_str.push_str(&hline);
-    table_str.push_str(
-        &[
-            "|",
-            &pad_center(header.0, day_col_size),
-            "|",
-            &pad_center(header.1, part1_col_size),
-            "|",
-            &pad_center(header.2, part2_col_size),
-            "|\n",
-        ]
-        .concat(),
-    );
-    table_str.push_str(&hline);
-    for (d, p1, p2) in table {
-        table_str.push_str(
-            &[
-                "| ",
-                &pad_left(&d, day_col_size - 2),
-                " |",
-                &p1.map(|(r, t)| {
-                    [
-                        " ",
-                        &pad_left(&r, part1_res_col_size),
-                        " (time: ",
-                        &pad_left(&t, part1_time_col_size),
-                        ") ",
-                    ]
-                    .concat()
-                })
-                .unwrap_or(repeat_char(' ', part1_col_size)),
-                "|",
-                &p2.map(|(r, t)| {
-                    [
-                        " ",
-                        &pad_left(&r, part2_res_col_size),
-                        " (time: ",
-                        &pad_left(&t, part2_time_col_size),
-                        ") ",
-                    ]
-                    .concat()
-                })
-                .unwrap_or(repeat_char(' ', part2_col_size)),
-                "|\n",
-            ]
-            .concat(),
-        )
     }
-    table_str.push_str(&hline);
-
-    // adding sections
-    let mut sections = vec![];
-    for (day, part, result) in multilines {
-        sections.push(format!(" === Day {day} part {part} ===\n\n{result}"))
-    }
-
-    // counting total time
-    let (p1_total, p2_total) =
-        results
-            .iter()
-            .fold((Duration::ZERO, Duration::ZERO), |(t1, t2), r| {
-                (
-                    t1 + r.p1.as_ref().map_or(Duration::ZERO, |p| p.time),
-                    t2 + r.p2.as_ref().map_or(Duration::ZERO, |p| p.time),
-                )
-            });
-    let time_totals = format!(
-        "Part 1: {}\nPart 2: {}\nTotal : {}\n",
-        format_duration(p1_total),
-        format_duration(p2_total),
-        format_duration(p1_total + p2_total)
-    );
+    Ok(to_run)
+}
 
-    // building the result
-    let mut result = vec![table_str];
-    result.extend(sections);
-    result.push(time_totals);
-    result.join("\n")
+/// Record the timings of the results in the history file, if asked
+fn record_history(args: &Args, results: &[DayResult]) -> Result<(), Box<dyn Error>> {
+    // only the inputs in the inputs dir can be told apart between runs
+    if !args.no_history && args.input.is_none() {
+        let input = if args.example {
+            EXAMPLE_INPUT
+        } else {
+            &args.variant
+        };
+        save_history(&args.history_file, input, results)?;
+    }
+    Ok(())
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args = parse_args()?;
+/// Show the results and run the visualizations
+///
+/// Fails if a part failed or an answer is wrong
+fn report(
+    args: &Args,
+    to_run: &[Option<DayInput>],
+    results: &[DayResult],
+    wall_time: Duration,
+    failed_checks: &[String],
+) -> Result<(), Box<dyn Error>> {
+    print!(
+        "{}",
+        match args.output {
+            OutputFormat::Table => result_table(results, wall_time),
+            OutputFormat::Json => result_json(results),
+            OutputFormat::Csv => result_csv(results),
+            OutputFormat::Markdown => result_markdown(results, wall_time),
+            OutputFormat::Html => result_html(results, wall_time),
+        }
+    );
 
-    let to_run = {
-        // deciding what to run
-        let mut to_run = vec![None; 25];
-        match args.day {
-            Some(d) => {
-                to_run[d as usize - 1] = Some((
-                    args.part,
-                    read_file(
-                        match args.input {
-                            Some(alternate_file) => PathBuf::from(alternate_file),
-                            None => get_input_from_input_dir(d, &args.inputs_dir.unwrap()),
-                        }
-                        .as_path(),
-                    )?,
-                ))
-            }
-            None => {
-                let path = args.inputs_dir.unwrap();
-                for d in 1..=25 {
-                    use PartSpec::*;
-                    let input = get_input_from_input_dir(d, &path);
-                    to_run[d as usize - 1] = match DAYS[d as usize - 1] {
-                        (None, None, _) => None,
-                        (Some(_), None, _) => Some((First, read_file(input.as_path())?)),
-                        (None, Some(_), _) => Some((Second, read_file(input.as_path())?)),
-                        (Some(_), Some(_), _) => Some((Both, read_file(input.as_path())?)),
-                    }
-                }
+    if !args.visualize.is_empty() {
+        for (d, run) in to_run.iter().enumerate() {
+            if let Some(DayInput {
+                loaded: Ok((input, _)),
+                ..
+            }) = run
+            {
+                run_visualizations(d as u8 + 1, &args.visualize, input, &args.visualize_dir)?;
             }
         }
-        to_run
-    };
+    }
 
-    let results: Vec<_> =
-        Result::from_iter(to_run.into_iter().enumerate().map(|(d, run)| match run {
-            Some((parts, input)) => run_day(d as u8 + 1, parts, &input),
-            None => Ok(DayResult { p1: None, p2: None }),
-        }))?;
+    let failed_parts: Vec<_> = result_records(results)
+        .filter_map(|(day, part, res)| res.as_ref().err().map(|err| (day, part, err.clone())))
+        .collect();
 
-    print!("{}", result_table(results));
+    for (day, part, err) in failed_parts.iter() {
+        eprintln!("Day {day} part {part} failed: {err}")
+    }
+    for fail in failed_checks.iter() {
+        eprintln!("{fail}")
+    }
+    match (failed_parts.len(), failed_checks.len()) {
+        (0, 0) => (),
+        (0, checks) => return Err(format!("{checks} answers are wrong").into()),
+        (parts, 0) => return Err(format!("{parts} parts failed").into()),
+        (parts, checks) => {
+            return Err(format!("{parts} parts failed and {checks} answers are wrong").into())
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,87 @@
+//! Measure of the heap used by the parts
+//!
+//! The measures need [`CountingAllocator`] to be the global allocator of the executable,
+//! otherwise they are all zero
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Allocator counting the allocations of each thread, to measure the memory used by the parts
+pub struct CountingAllocator;
+
+thread_local! {
+    /// Bytes allocated by this thread, their peak since the last measure and the number of allocations
+    ///
+    /// Memory freed by another thread than the one that allocated it makes the count go below zero
+    static HEAP: Cell<(isize, isize, usize)> = const { Cell::new((0, 0, 0)) };
+}
+
+impl CountingAllocator {
+    fn record(size: isize, allocations: usize) {
+        // the thread could be shutting down
+        let _ = HEAP.try_with(|heap| {
+            let (current, peak, count) = heap.get();
+            let current = current + size;
+            heap.set((current, peak.max(current), count + allocations))
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size() as isize, 1)
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size() as isize, 1)
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record(-(layout.size() as isize), 0)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::record(new_size as isize - layout.size() as isize, 1)
+        }
+        new_ptr
+    }
+}
+
+/// Heap used while running some code
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemoryUsage {
+    /// Most bytes allocated at once, over what was allocated before
+    pub peak: usize,
+    pub allocations: usize,
+}
+
+/// Run a function, measuring the heap it uses on this thread
+pub fn measure_memory<T>(f: impl FnOnce() -> T) -> (T, MemoryUsage) {
+    let (start, start_count) = HEAP.with(|heap| {
+        let (current, _, count) = heap.get();
+        heap.set((current, current, count));
+        (current, count)
+    });
+    let res = f();
+    let (_, peak, count) = HEAP.with(|heap| heap.get());
+    (
+        res,
+        MemoryUsage {
+            peak: (peak - start) as usize,
+            allocations: count - start_count,
+        },
+    )
+}
@@ -0,0 +1,914 @@
+//! Reports of the results of a run, and of the other commands
+
+use std::time::Duration;
+
+use crate::{
+    bench::BenchRecord,
+    runner::{Check, DayResult, PartFailure, PartOutcome},
+    PuzzleResult, ResultRepr,
+};
+
+/// Center a string
+fn pad_center(s: &str, len: usize) -> String {
+    if s.len() >= len {
+        String::from(s)
+    } else {
+        let pad = (len - s.len()) / 2;
+        let mut res = String::with_capacity(len);
+        for _ in 0..pad {
+            res.push(' ')
+        }
+        res.push_str(s);
+        while res.len() < len {
+            res.push(' ')
+        }
+        res
+    }
+}
+/// left pad a string
+fn pad_left(s: &str, len: usize) -> String {
+    if s.len() >= len {
+        String::from(s)
+    } else {
+        let pad = len - s.len();
+        let mut res = String::with_capacity(len);
+        for _ in 0..pad {
+            res.push(' ')
+        }
+        res.push_str(s);
+        res
+    }
+}
+///create a string with a repeated char
+fn repeat_char(ch: char, len: usize) -> String {
+    let mut s = String::with_capacity(ch.len_utf8() * len);
+    for _ in 0..len {
+        s.push(ch)
+    }
+    s
+}
+
+/// Format a number of bytes
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.;
+    let mut unit = 0;
+    while value >= 1024. && unit < UNITS.len() - 1 {
+        value /= 1024.;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Format a duration
+pub fn format_duration(d: Duration) -> String {
+    // humantime::format_duration(d).to_string()
+    if d.as_secs() > 60 {
+        // writing it as {}m {}s
+        format!("{}m {:2}s", d.as_secs() / 60, d.as_secs() % 60)
+    } else if d.as_secs() > 0 {
+        // writing it as {}s {}ms
+        format!("{}s {:3}ms", d.as_secs(), d.subsec_millis())
+    } else if d.as_millis() > 0 {
+        // writing it as {}ms {}us
+        format!("{}ms {:3}us", d.as_millis(), d.subsec_micros() % 1000)
+    } else if d.as_micros() > 0 {
+        // writing it as {}us {}ns
+        format!("{}us {:3}ns", d.as_micros(), d.subsec_nanos() % 1000)
+    } else {
+        // writing it as {}ns
+        format!("{}ns", d.as_nanos())
+    }
+}
+
+/// Header of the column of a part
+fn part_column_name(part: usize) -> String {
+    const NAMES: [&str; 5] = ["one", "two", "three", "four", "five"];
+    match NAMES.get(part - 1) {
+        Some(name) => format!("part {name}"),
+        None => format!("part {part}"),
+    }
+}
+
+/// Name of the column with the memory used by a part
+fn memory_column_name(part: usize) -> String {
+    format!("{} memory", part_column_name(part))
+}
+
+/// Parts that were run for some day, the only ones getting a column
+fn shown_parts(results: &[DayResult]) -> Vec<usize> {
+    let max_parts = results.iter().map(|r| r.parts.len()).max().unwrap_or(0);
+    (1..=max_parts)
+        .filter(|&part| {
+            results
+                .iter()
+                .any(|r| matches!(r.parts.get(part - 1), Some(Some(_))))
+        })
+        .collect()
+}
+
+pub fn result_table(results: &[DayResult], wall_time: Duration) -> String {
+    let shown_parts = shown_parts(results);
+    let mut table = Vec::with_capacity(results.len());
+    let mut multilines = vec![];
+
+    let memory_entry = |res: &PartOutcome| -> String {
+        match res {
+            Ok(res) => format!(
+                "{} ({} allocs)",
+                format_bytes(res.memory.peak),
+                res.memory.allocations
+            ),
+            Err(_) => "-".to_owned(),
+        }
+    };
+    let mut part_entry = |day: usize, part: usize, res: &PartOutcome| -> (String, String) {
+        let res = match res {
+            Ok(res) => res,
+            Err(PartFailure::Error(err)) => {
+                return (
+                    format!("ERROR: {}", err.lines().next().unwrap_or_default()),
+                    "-".to_owned(),
+                )
+            }
+            Err(PartFailure::Timeout(time)) => {
+                return (
+                    "TIMEOUT".to_owned(),
+                    format!("> {}", format_duration(*time)),
+                )
+            }
+        };
+        let repr = match res.res.repr() {
+            ResultRepr::Short(s) => s,
+            ResultRepr::Multiline(s) => {
+                multilines.push((day, part, s));
+                "<multiline>".to_owned()
+            }
+        };
+        let repr = match &res.check {
+            Some(check) => format!("{repr} {}", check.mark()),
+            None => repr,
+        };
+        (
+            if res.changed {
+                format!("{repr} [changed]")
+            } else {
+                repr
+            },
+            if res.cached {
+                format!("{}, cached", format_duration(res.time))
+            } else {
+                format_duration(res.time)
+            },
+        )
+    };
+
+    for (d, res) in results.iter().enumerate() {
+        // erase empty lines
+        if res.parts.iter().any(|p| p.is_some()) {
+            table.push((
+                (d + 1).to_string(),
+                res.parse_time
+                    .map_or("-".to_owned(), |t| format_duration(t).to_string()),
+                shown_parts
+                    .iter()
+                    .map(|&part| {
+                        res.parts
+                            .get(part - 1)
+                            .and_then(|p| p.as_ref())
+                            .map(|p| part_entry(d + 1, part, p))
+                    })
+                    .collect::<Vec<_>>(),
+                shown_parts
+                    .iter()
+                    .map(|&part| {
+                        res.parts
+                            .get(part - 1)
+                            .and_then(|p| p.as_ref())
+                            .map(memory_entry)
+                    })
+                    .collect::<Vec<_>>(),
+            ))
+        }
+    }
+
+    // measuring column sizes
+    let day_col_size = table
+        .iter()
+        .map(|(s, _, _, _)| s.len())
+        .fold("day".len(), usize::max);
+    let parse_col_size = table
+        .iter()
+        .map(|(_, s, _, _)| s.len())
+        .fold("parse".len(), usize::max);
+    let res_col_sizes: Vec<usize> = (0..shown_parts.len())
+        .map(|i| {
+            table
+                .iter()
+                .filter_map(|(_, _, parts, _)| parts[i].as_ref().map(|(s, _)| s.len()))
+                .fold(0, usize::max)
+        })
+        .collect();
+    let time_col_sizes: Vec<usize> = (0..shown_parts.len())
+        .map(|i| {
+            table
+                .iter()
+                .filter_map(|(_, _, parts, _)| parts[i].as_ref().map(|(_, s)| s.len()))
+                .fold(0, usize::max)
+        })
+        .collect();
+    let memory_col_sizes: Vec<usize> = shown_parts
+        .iter()
+        .enumerate()
+        .map(|(i, &part)| {
+            table
+                .iter()
+                .filter_map(|(_, _, _, memory)| memory[i].as_ref().map(|s| s.len()))
+                .fold(memory_column_name(part).len(), usize::max)
+                + 2
+        })
+        .collect();
+
+    // measuring complete column size
+    let day_col_size = day_col_size + 2;
+    let parse_col_size = parse_col_size + 2;
+    let part_col_sizes: Vec<usize> = res_col_sizes
+        .iter()
+        .zip(time_col_sizes.iter())
+        .map(|(res, time)| res + " (time: ".len() + time + ")".len() + 2)
+        .collect();
+
+    // preparing header
+    let mut hline = [
+        "+",
+        &repeat_char('-', day_col_size),
+        "+",
+        &repeat_char('-', parse_col_size),
+        "+",
+    ]
+    .concat();
+    for size in part_col_sizes.iter().chain(memory_col_sizes.iter()) {
+        hline.push_str(&repeat_char('-', *size));
+        hline.push('+')
+    }
+    hline.push('\n');
+
+    // build the table
+    let mut table_str = String::new();
+    table_str.push_str(&hline);
+    table_str.push_str(
+        &[
+            "|",
+            &pad_center("day", day_col_size),
+            "|",
+            &pad_center("parse", parse_col_size),
+            "|",
+        ]
+        .concat(),
+    );
+    for (i, size) in part_col_sizes.iter().enumerate() {
+        table_str.push_str(&pad_center(&part_column_name(shown_parts[i]), *size));
+        table_str.push('|')
+    }
+    for (i, size) in memory_col_sizes.iter().enumerate() {
+        table_str.push_str(&pad_center(&memory_column_name(shown_parts[i]), *size));
+        table_str.push('|')
+    }
+    table_str.push('\n');
+    table_str.push_str(&hline);
+    for (d, parse, parts, memory) in table {
+        table_str.push_str(
+            &[
+                "| ",
+                &pad_left(&d, day_col_size - 2),
+                " | ",
+                &pad_left(&parse, parse_col_size - 2),
+                " |",
+            ]
+            .concat(),
+        );
+        for (i, part) in parts.into_iter().enumerate() {
+            table_str.push_str(
+                &part
+                    .map(|(r, t)| {
+                        [
+                            " ",
+                            &pad_left(&r, res_col_sizes[i]),
+                            " (time: ",
+                            &pad_left(&t, time_col_sizes[i]),
+                            ") ",
+                        ]
+                        .concat()
+                    })
+                    .unwrap_or(repeat_char(' ', part_col_sizes[i])),
+            );
+            table_str.push('|')
+        }
+        for (i, memory) in memory.into_iter().enumerate() {
+            table_str.push_str(
+                &memory
+                    .map(|m| [" ", &pad_left(&m, memory_col_sizes[i] - 2), " "].concat())
+                    .unwrap_or(repeat_char(' ', memory_col_sizes[i])),
+            );
+            table_str.push('|')
+        }
+        table_str.push('\n')
+    }
+    table_str.push_str(&hline);
+
+    // adding sections
+    let mut sections = vec![];
+    for (day, part, result) in multilines {
+        sections.push(format!(" === Day {day} part {part} ===\n\n{result}"))
+    }
+
+    // counting total time
+    let part_totals: Vec<Duration> = shown_parts
+        .iter()
+        .map(|&part| {
+            results
+                .iter()
+                .filter_map(|r| r.parts.get(part - 1).and_then(|p| p.as_ref()))
+                .map(|p| p.as_ref().map_or(Duration::ZERO, |p| p.time))
+                .sum()
+        })
+        .collect();
+    let parse_total: Duration = results.iter().filter_map(|r| r.parse_time).sum();
+    let mut time_totals = format!("Parse : {}\n", format_duration(parse_total));
+    for (part, total) in shown_parts.iter().zip(part_totals.iter()) {
+        time_totals.push_str(&format!("Part {part}: {}\n", format_duration(*total)))
+    }
+    time_totals.push_str(&format!(
+        "Total : {}\nWall  : {}\n",
+        format_duration(parse_total + part_totals.iter().sum::<Duration>()),
+        format_duration(wall_time)
+    ));
+
+    // building the result
+    let mut result = vec![table_str];
+    result.extend(sections);
+    result.push(time_totals);
+    result.join("\n")
+}
+
+/// All the parts that were run, as (day, part, result)
+pub fn result_records(results: &[DayResult]) -> impl Iterator<Item = (usize, usize, &PartOutcome)> {
+    results.iter().enumerate().flat_map(|(d, res)| {
+        res.parts
+            .iter()
+            .enumerate()
+            .filter_map(move |(i, p)| p.as_ref().map(|p| (d + 1, i + 1, p)))
+    })
+}
+
+/// Kind of a failed result, in the JSON and CSV outputs
+fn failure_kind(failure: &PartFailure) -> &'static str {
+    match failure {
+        PartFailure::Error(_) => "error",
+        PartFailure::Timeout(_) => "timeout",
+    }
+}
+
+/// Quote a string as a JSON string literal
+fn json_string(s: &str) -> String {
+    let mut res = String::with_capacity(s.len() + 2);
+    res.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            '\r' => res.push_str("\\r"),
+            '\t' => res.push_str("\\t"),
+            ch if ch.is_control() => res.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => res.push(ch),
+        }
+    }
+    res.push('"');
+    res
+}
+
+pub fn result_json(results: &[DayResult]) -> String {
+    let records: Vec<_> = result_records(results)
+        .map(|(day, part, res)| {
+            let res = match res {
+                Ok(res) => res,
+                Err(err) => {
+                    return format!(
+                        "  {{\"day\": {day}, \"part\": {part}, \"kind\": \"{}\", \"value\": {}, \"time_ns\": null, \"peak_bytes\": null, \"allocations\": null, \"cached\": false, \"changed\": false, \"check\": null}}",
+                        failure_kind(err),
+                        json_string(&err.to_string())
+                    )
+                }
+            };
+            let value = match &res.res {
+                PuzzleResult::Numeric(v) => v.to_string(),
+                PuzzleResult::Textual(s) | PuzzleResult::AsciiArt(s) => json_string(s),
+            };
+            let check = match &res.check {
+                Some(check) => json_string(check.name()),
+                None => "null".to_owned(),
+            };
+            format!(
+                "  {{\"day\": {day}, \"part\": {part}, \"kind\": \"{}\", \"value\": {value}, \"time_ns\": {}, \"peak_bytes\": {}, \"allocations\": {}, \"cached\": {}, \"changed\": {}, \"check\": {check}}}",
+                res.res.kind(),
+                res.time.as_nanos(),
+                res.memory.peak,
+                res.memory.allocations,
+                res.cached,
+                res.changed
+            )
+        })
+        .collect();
+    if records.is_empty() {
+        "[]\n".to_owned()
+    } else {
+        format!("[\n{}\n]\n", records.join(",\n"))
+    }
+}
+
+/// Quote a CSV field, if needed
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_owned()
+    }
+}
+
+pub fn result_csv(results: &[DayResult]) -> String {
+    let mut csv =
+        String::from("day,part,kind,value,time_ns,peak_bytes,allocations,cached,changed,check\n");
+    for (day, part, res) in result_records(results) {
+        let res = match res {
+            Ok(res) => res,
+            Err(err) => {
+                csv.push_str(&format!(
+                    "{day},{part},{},{},,,,false,false,\n",
+                    failure_kind(err),
+                    csv_field(&err.to_string())
+                ));
+                continue;
+            }
+        };
+        let value = match &res.res {
+            PuzzleResult::Numeric(v) => v.to_string(),
+            PuzzleResult::Textual(s) | PuzzleResult::AsciiArt(s) => csv_field(s),
+        };
+        csv.push_str(&format!(
+            "{day},{part},{},{value},{},{},{},{},{},{}\n",
+            res.res.kind(),
+            res.time.as_nanos(),
+            res.memory.peak,
+            res.memory.allocations,
+            res.cached,
+            res.changed,
+            res.check.as_ref().map_or("", |c| c.name())
+        ));
+    }
+    csv
+}
+
+/// Answer of a part for the reports, with its check mark and if it changed, or why it failed
+///
+/// Multiline answers are given apart, to be shown in their own section
+pub fn report_answer(res: &PartOutcome) -> (String, Option<String>) {
+    match res {
+        Ok(res) => {
+            let (answer, multiline) = match res.res.repr() {
+                ResultRepr::Short(s) => (s, None),
+                ResultRepr::Multiline(s) => ("see below".to_owned(), Some(s)),
+            };
+            let answer = match &res.check {
+                Some(check) => format!("{answer} {}", check.mark()),
+                None => answer,
+            };
+            let answer = if res.changed {
+                format!("{answer} [changed]")
+            } else {
+                answer
+            };
+            (answer, multiline)
+        }
+        Err(PartFailure::Error(err)) => (
+            format!("ERROR: {}", err.lines().next().unwrap_or_default()),
+            None,
+        ),
+        Err(PartFailure::Timeout(_)) => ("TIMEOUT".to_owned(), None),
+    }
+}
+
+/// Time of a part for the reports
+pub fn report_time(res: &PartOutcome) -> String {
+    match res {
+        Ok(res) if res.cached => format!("{} (cached)", format_duration(res.time)),
+        Ok(res) => format_duration(res.time),
+        Err(PartFailure::Timeout(time)) => format!("> {}", format_duration(*time)),
+        Err(PartFailure::Error(_)) => "-".to_owned(),
+    }
+}
+
+/// Title of a day, if it is solved
+pub fn day_title(day: usize) -> &'static str {
+    crate::day(day as u8).map_or("", |d| d.title())
+}
+
+/// Total time spent parsing and solving
+fn total_time(results: &[DayResult]) -> Duration {
+    result_records(results)
+        .filter_map(|(_, _, res)| res.as_ref().ok().map(|res| res.time))
+        .chain(results.iter().filter_map(|r| r.parse_time))
+        .sum()
+}
+
+/// Escape a cell of a markdown table
+///
+/// A row must fit on one line, so the line breaks are written as `<br>`
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace(['\n', '\r'], "<br>")
+}
+
+/// A GitHub flavored markdown table, with the multiline answers in code blocks after it
+pub fn result_markdown(results: &[DayResult], wall_time: Duration) -> String {
+    let shown_parts = shown_parts(results);
+    let mut md = String::from("| day | title | parse |");
+    let mut align = String::from("|----:|:------|------:|");
+    for &part in shown_parts.iter() {
+        let name = part_column_name(part);
+        md.push_str(&format!(" {name} | {name} time |"));
+        align.push_str("------:|------:|");
+    }
+    md.push('\n');
+    md.push_str(&align);
+    md.push('\n');
+
+    let mut sections = vec![];
+    for (d, res) in results.iter().enumerate() {
+        if res.parts.iter().all(|p| p.is_none()) {
+            continue;
+        }
+        md.push_str(&format!(
+            "| {} | {} | {} |",
+            d + 1,
+            markdown_cell(day_title(d + 1)),
+            res.parse_time.map_or("-".to_owned(), format_duration)
+        ));
+        for &part in shown_parts.iter() {
+            match res.parts.get(part - 1).and_then(|p| p.as_ref()) {
+                Some(outcome) => {
+                    let (answer, multiline) = report_answer(outcome);
+                    if let Some(multiline) = multiline {
+                        sections.push(format!(
+                            "### Day {} part {part}\n\n```\n{}\n```\n",
+                            d + 1,
+                            multiline.trim_end_matches('\n')
+                        ))
+                    }
+                    md.push_str(&format!(
+                        " {} | {} |",
+                        markdown_cell(&answer),
+                        report_time(outcome)
+                    ))
+                }
+                None => md.push_str("  |  |"),
+            }
+        }
+        md.push('\n')
+    }
+    md.push_str(&format!(
+        "\nTotal time: {}, wall time: {}\n",
+        format_duration(total_time(results)),
+        format_duration(wall_time)
+    ));
+    for section in sections {
+        md.push('\n');
+        md.push_str(&section)
+    }
+    md
+}
+
+/// Escape a string for HTML
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// A standalone HTML page with the results, a bar showing the time of each part
+pub fn result_html(results: &[DayResult], wall_time: Duration) -> String {
+    let shown_parts = shown_parts(results);
+    // bars are relative to the slowest part
+    let slowest = result_records(results)
+        .filter_map(|(_, _, res)| res.as_ref().ok().map(|res| res.time))
+        .max()
+        .unwrap_or_default();
+
+    let mut html = String::from(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Advent of Code 2022</title>
+<style>
+body { font-family: sans-serif; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 4px 8px; vertical-align: top; }
+td.answer { font-family: monospace; text-align: right; }
+.fail { color: #c00; }
+.time { font-size: small; color: #555; }
+.bar { height: 4px; background: #48c; }
+pre { font-family: monospace; line-height: 1; }
+</style>
+</head>
+<body>
+<h1>Advent of Code 2022</h1>
+<table>
+<tr><th>day</th><th>title</th><th>parse</th>"#,
+    );
+    for &part in shown_parts.iter() {
+        html.push_str(&format!("<th>{}</th>", part_column_name(part)))
+    }
+    html.push_str("</tr>\n");
+
+    let mut sections = vec![];
+    for (d, res) in results.iter().enumerate() {
+        if res.parts.iter().all(|p| p.is_none()) {
+            continue;
+        }
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td class=\"time\">{}</td>",
+            d + 1,
+            html_escape(day_title(d + 1)),
+            res.parse_time.map_or("-".to_owned(), format_duration)
+        ));
+        for &part in shown_parts.iter() {
+            let Some(outcome) = res.parts.get(part - 1).and_then(|p| p.as_ref()) else {
+                html.push_str("<td></td>");
+                continue;
+            };
+            let (answer, multiline) = report_answer(outcome);
+            if let Some(multiline) = multiline {
+                sections.push(format!(
+                    "<h2>Day {} part {part}</h2>\n<pre>{}</pre>\n",
+                    d + 1,
+                    html_escape(&multiline)
+                ))
+            }
+            let failed = match outcome {
+                Ok(res) => matches!(res.check, Some(Check::Fail { .. })),
+                Err(_) => true,
+            };
+            let width = match outcome {
+                Ok(res) if !slowest.is_zero() => {
+                    res.time.as_secs_f64() / slowest.as_secs_f64() * 100.
+                }
+                _ => 0.,
+            };
+            html.push_str(&format!(
+                "<td class=\"answer{}\">{}<div class=\"bar\" style=\"width: {width:.1}%\"></div><div class=\"time\">{}</div></td>",
+                if failed { " fail" } else { "" },
+                html_escape(&answer),
+                html_escape(&report_time(outcome))
+            ))
+        }
+        html.push_str("</tr>\n")
+    }
+    html.push_str(&format!(
+        "</table>\n<p>Total time: {}, wall time: {}</p>\n",
+        format_duration(total_time(results)),
+        format_duration(wall_time)
+    ));
+    for section in sections {
+        html.push_str(&section)
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+pub fn bench_table(stats: &[BenchRecord], baseline: Option<&[BenchRecord]>) -> String {
+    let mut header = vec!["day", "part", "runs", "min", "median", "mean", "stddev"];
+    if baseline.is_some() {
+        header.push("vs baseline");
+    }
+    let rows: Vec<Vec<String>> = stats
+        .iter()
+        .map(|(day, part, s)| {
+            let mut row = vec![
+                day.to_string(),
+                match part {
+                    0 => "parse".to_owned(),
+                    part => part.to_string(),
+                },
+                s.runs.to_string(),
+                format_duration(s.min),
+                format_duration(s.median),
+                format_duration(s.mean),
+                format_duration(s.stddev),
+            ];
+            if let Some(baseline) = baseline {
+                // comparing the medians, the most robust to outliers
+                row.push(
+                    match baseline.iter().find(|(d, p, _)| d == day && p == part) {
+                        Some((_, _, b)) if !b.median.is_zero() => {
+                            let change =
+                                (s.median.as_secs_f64() / b.median.as_secs_f64() - 1.) * 100.;
+                            format!("{change:+.1}%")
+                        }
+                        _ => "-".to_owned(),
+                    },
+                )
+            }
+            row
+        })
+        .collect();
+
+    simple_table(&header, &rows)
+}
+
+/// Draw a table with a header, right aligning the cells
+pub(crate) fn simple_table(header: &[&str], rows: &[Vec<String>]) -> String {
+    // measuring column sizes
+    let sizes: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(i, h)| rows.iter().map(|r| r[i].len()).fold(h.len(), usize::max) + 2)
+        .collect();
+
+    let hline = {
+        let mut hline = String::from("+");
+        for size in sizes.iter() {
+            hline.push_str(&repeat_char('-', *size));
+            hline.push('+')
+        }
+        hline.push('\n');
+        hline
+    };
+
+    let mut table_str = hline.clone();
+    table_str.push('|');
+    for (h, size) in header.iter().zip(sizes.iter()) {
+        table_str.push_str(&pad_center(h, *size));
+        table_str.push('|')
+    }
+    table_str.push('\n');
+    table_str.push_str(&hline);
+    for row in rows {
+        table_str.push('|');
+        for (cell, size) in row.iter().zip(sizes.iter()) {
+            table_str.push(' ');
+            table_str.push_str(&pad_left(cell, size - 2));
+            table_str.push_str(" |")
+        }
+        table_str.push('\n');
+    }
+    table_str.push_str(&hline);
+    table_str
+}
+
+/// Describe all the solved days
+pub fn days_list() -> String {
+    let mut list = String::new();
+    for day in crate::DAYS.iter() {
+        let parts = day.parts();
+        list.push_str(&format!(
+            "Day {:2}: {} ({parts} {})\n",
+            day.number(),
+            day.title(),
+            if parts == 1 { "part" } else { "parts" }
+        ));
+        for param in day.params() {
+            list.push_str(&format!(
+                "    param {}={}: {}\n",
+                param.name, param.default, param.description
+            ))
+        }
+        for name in day.visualizations().keys() {
+            list.push_str(&format!("    visualization day{}/{name}\n", day.number()))
+        }
+    }
+    list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Strings that need escaping in some format
+    const AWKWARD: &[&str] = &[
+        "",
+        "plain",
+        "#..#\n#..#\n####\n",
+        "say \"hi\"",
+        "back\\slash",
+        "a,b,,c",
+        "tab\tbell\u{7}nul\0",
+        "crlf\r\n",
+        "unicode \u{e9}",
+    ];
+
+    /// Read back a JSON string literal
+    fn parse_json_string(literal: &str) -> String {
+        let inner = literal
+            .strip_prefix('"')
+            .and_then(|s| s.strip_suffix('"'))
+            .expect("a quoted string");
+        let mut res = String::new();
+        let mut chars = inner.chars();
+        while let Some(ch) = chars.next() {
+            match ch {
+                '\\' => match chars.next().unwrap() {
+                    '"' => res.push('"'),
+                    '\\' => res.push('\\'),
+                    '/' => res.push('/'),
+                    'n' => res.push('\n'),
+                    'r' => res.push('\r'),
+                    't' => res.push('\t'),
+                    'b' => res.push('\u{8}'),
+                    'f' => res.push('\u{c}'),
+                    'u' => {
+                        let code: String = chars.by_ref().take(4).collect();
+                        res.push(char::from_u32(u32::from_str_radix(&code, 16).unwrap()).unwrap())
+                    }
+                    ch => panic!("invalid escape \\{ch} in {literal}"),
+                },
+                '"' => panic!("unescaped quote in {literal}"),
+                ch if ch.is_control() => panic!("unescaped control character in {literal}"),
+                ch => res.push(ch),
+            }
+        }
+        res
+    }
+
+    /// Read back a field of a CSV line, checking it does not end early
+    fn parse_csv_field(field: &str) -> String {
+        match field.strip_prefix('"') {
+            Some(quoted) => {
+                let inner = quoted.strip_suffix('"').expect("a closing quote");
+                assert!(
+                    !inner.replace("\"\"", "").contains('"'),
+                    "unescaped quote in {field}"
+                );
+                inner.replace("\"\"", "\"")
+            }
+            None => {
+                assert!(
+                    !field.contains([',', '"', '\n', '\r']),
+                    "unquoted separator in {field}"
+                );
+                field.to_owned()
+            }
+        }
+    }
+
+    #[test]
+    fn json_strings() {
+        assert_eq!(json_string("#.\n.#\n"), r##""#.\n.#\n""##);
+        assert_eq!(json_string("a\"b\\c"), r#""a\"b\\c""#);
+        assert_eq!(json_string("\u{7}"), r#""\u0007""#);
+        for s in AWKWARD {
+            assert_eq!(parse_json_string(&json_string(s)), *s, "{s:?}")
+        }
+    }
+
+    #[test]
+    fn csv_fields() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("#.\n.#\n"), "\"#.\n.#\n\"");
+        for s in AWKWARD {
+            assert_eq!(parse_csv_field(&csv_field(s)), *s, "{s:?}")
+        }
+    }
+
+    #[test]
+    fn markdown_cells() {
+        assert_eq!(markdown_cell("plain"), "plain");
+        assert_eq!(markdown_cell("a|b"), "a\\|b");
+        // a line break would end the row
+        assert_eq!(
+            markdown_cell("one\ntwo\r\nthree\n"),
+            "one<br>two<br>three<br>"
+        );
+        for s in AWKWARD {
+            assert!(!markdown_cell(s).contains(['\n', '\r']), "{s:?}")
+        }
+    }
+
+    #[test]
+    fn html_escapes() {
+        assert_eq!(html_escape("plain"), "plain");
+        assert_eq!(
+            html_escape("<b class=\"x\">A & B</b>"),
+            "&lt;b class=&quot;x&quot;&gt;A &amp; B&lt;/b&gt;"
+        );
+        // an entity in the text is shown as written
+        assert_eq!(html_escape("&lt;"), "&amp;lt;");
+    }
+}
@@ -0,0 +1,771 @@
+//! Running of the parts: parsing the inputs once, solving the parts in parallel or in worker
+//! processes, and checking the answers
+
+use std::{
+    cell::Cell,
+    env,
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    io::{self, Read, Write},
+    mem, panic,
+    path::{Path, PathBuf},
+    process::{self, Stdio},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Once,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+use regex::Regex;
+
+use crate::{
+    cache::Cache,
+    dashboard::{Dashboard, PartStatus},
+    inputs::{get_related_path, read_file, DayInput},
+    memory::{measure_memory, MemoryUsage},
+    report::format_duration,
+    Day, Params, ParseError, Parsed, PuzzleResult, ResultRepr,
+};
+
+/// Parts to run of each day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartSpec {
+    All,
+    Only(usize),
+}
+
+/// Outcome of the check of a result against the known answer
+#[derive(Debug, Clone)]
+pub enum Check {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+impl Check {
+    pub fn mark(&self) -> &'static str {
+        match self {
+            Check::Pass => "[ok]",
+            Check::Fail { .. } => "[FAIL]",
+            Check::Unknown => "[??]",
+        }
+    }
+    pub fn name(&self) -> &'static str {
+        match self {
+            Check::Pass => "pass",
+            Check::Fail { .. } => "fail",
+            Check::Unknown => "unknown",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RunResult {
+    pub res: PuzzleResult,
+    pub time: Duration,
+    pub memory: MemoryUsage,
+    /// The result was taken from the cache, and the measures are from when it was solved
+    pub cached: bool,
+    /// The result is different from the one of the previous run, when watching
+    pub changed: bool,
+    pub check: Option<Check>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DayResult {
+    /// Time spent parsing the input, if it was parsed successfully
+    pub parse_time: Option<Duration>,
+    /// Outcomes of the parts, in order
+    pub parts: Vec<Option<PartOutcome>>,
+}
+
+/// Why a part gave no result
+#[derive(Debug, Clone)]
+pub enum PartFailure {
+    /// The message of the error that made it fail
+    Error(String),
+    /// It was killed after running for this time
+    Timeout(Duration),
+}
+impl Display for PartFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PartFailure::Error(msg) => write!(f, "{msg}"),
+            PartFailure::Timeout(time) => write!(f, "Timed out after {}", format_duration(*time)),
+        }
+    }
+}
+
+/// Result of a part, or the reason it failed
+pub type PartOutcome = Result<RunResult, PartFailure>;
+
+thread_local! {
+    /// The panics of this thread are caught, and reported as errors
+    static CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Silence the panics that are caught, leaving the others to the default hook
+fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING_PANICS.with(|catching| catching.get()) {
+                default_hook(info)
+            }
+        }))
+    })
+}
+
+/// Run a function, turning its panics into errors
+///
+/// The panics are not printed, their message is in the error
+pub fn catch_panics<T>(f: impl FnOnce() -> Result<T, Box<dyn Error>>) -> Result<T, Box<dyn Error>> {
+    install_panic_hook();
+    let was_catching = CATCHING_PANICS.with(|catching| catching.replace(true));
+    let res = panic::catch_unwind(panic::AssertUnwindSafe(f));
+    CATCHING_PANICS.with(|catching| catching.set(was_catching));
+    res.unwrap_or_else(|payload| {
+        let msg = if let Some(msg) = payload.downcast_ref::<&str>() {
+            msg.to_string()
+        } else if let Some(msg) = payload.downcast_ref::<String>() {
+            msg.clone()
+        } else {
+            "unknown cause".to_owned()
+        };
+        Err(format!("Panicked: {msg}").into())
+    })
+}
+
+/// Show the line of the input where a parse error was found, with a caret under it
+fn show_parse_error(err: Box<dyn Error>, input: &str) -> Box<dyn Error> {
+    let Some(err) = err.downcast_ref::<ParseError>() else {
+        return err;
+    };
+    let Some(line) = err.line.checked_sub(1).and_then(|l| input.lines().nth(l)) else {
+        return err.to_string().into();
+    };
+    let number = err.line.to_string();
+    let margin = " ".repeat(number.len());
+    format!(
+        "{err}\n{margin} |\n{number} | {line}\n{margin} | {:>column$}",
+        "^",
+        column = err.column
+    )
+    .into()
+}
+
+/// Parse the input of a day, measuring the time it took
+pub fn parse_day<'a>(
+    day: &dyn Day,
+    input: &'a str,
+    params: &Params,
+) -> Result<(Box<dyn Parsed + 'a>, Duration), Box<dyn Error>> {
+    let start = Instant::now();
+    let parsed =
+        catch_panics(|| day.parse(input, params)).map_err(|err| show_parse_error(err, input))?;
+    Ok((parsed, start.elapsed()))
+}
+
+/// Solve a part on a parsed input, with the input to show the errors found by the part
+pub fn run_part(
+    parsed: &dyn Parsed,
+    part: usize,
+    input: &str,
+) -> Result<RunResult, Box<dyn Error>> {
+    let start = Instant::now();
+    let (res, memory) = measure_memory(|| catch_panics(|| parsed.solve(part)));
+    let time = start.elapsed();
+    Ok(RunResult {
+        res: res.map_err(|err| show_parse_error(err, input))?,
+        time,
+        memory,
+        cached: false,
+        changed: false,
+        check: None,
+    })
+}
+
+/// Run `f` on all the tasks, spreading them over `jobs` threads
+///
+/// Results are in the order of the tasks, whatever order they completed in
+fn run_pool<T: Sync, R: Send>(jobs: usize, tasks: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    // workers pick the next task until none is left
+    let next_task = AtomicUsize::new(0);
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(tasks.len()))
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = vec![];
+                    loop {
+                        let idx = next_task.fetch_add(1, Ordering::Relaxed);
+                        if idx >= tasks.len() {
+                            return results;
+                        }
+                        results.push((idx, f(&tasks[idx])))
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Worker threads should not panic"))
+            .collect()
+    });
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, res)| res).collect()
+}
+
+/// Find the requested parts of a day
+pub fn selected_parts(day: &dyn Day, parts: PartSpec) -> Result<Vec<usize>, Box<dyn Error>> {
+    let number = day.number();
+    match parts {
+        PartSpec::All if day.parts() == 0 => Err(format!("Day {number} is unsolved").into()),
+        PartSpec::All => Ok((1..=day.parts()).collect()),
+        PartSpec::Only(part) if part <= day.parts() => Ok(vec![part]),
+        PartSpec::Only(part) => Err(format!("Part {part} of day {number} is unsolved").into()),
+    }
+}
+
+/// Run all the requested parts, spreading them over `jobs` threads
+///
+/// Each input is parsed once, and shared between the parts of its day. With a timeout
+/// each part runs in a worker process instead, parsing the input on its own
+pub fn run_days(
+    to_run: &[Option<DayInput>],
+    jobs: usize,
+    timeout: Option<Duration>,
+    dashboard: Option<&Dashboard>,
+) -> Result<Vec<DayResult>, Box<dyn Error>> {
+    if let Some(timeout) = timeout {
+        return run_days_isolated(to_run, jobs, timeout, dashboard);
+    }
+    let mut results = vec![DayResult::default(); to_run.len()];
+
+    // listing all the days to parse
+    let mut parse_tasks = vec![];
+    for (d, run) in to_run.iter().enumerate() {
+        if let Some(run) = run {
+            let day =
+                crate::day(d as u8 + 1).ok_or_else(|| format!("Day {} is unsolved", d + 1))?;
+            let parts = run.parts.clone();
+            match &run.loaded {
+                Ok((input, params)) => parse_tasks.push((d, day, parts, input.as_str(), params)),
+                // no use in running it
+                Err(err) => {
+                    for part in parts {
+                        set_outcome(&mut results[d], part, Err(PartFailure::Error(err.clone())))
+                    }
+                }
+            }
+        }
+    }
+    let parsed = run_pool(jobs, &parse_tasks, |(d, day, parts, input, params)| {
+        // the other parts could be already shown as solved from the cache
+        let set_parts = |status: PartStatus| {
+            if let Some(db) = dashboard {
+                for &part in parts {
+                    db.set(*d, Some(part), status.clone())
+                }
+            }
+        };
+        set_parts(PartStatus::Parsing(Instant::now()));
+        let parsed = parse_day(*day, input, params).map_err(|err| err.to_string());
+        match &parsed {
+            Ok(_) => set_parts(PartStatus::Pending),
+            Err(err) => set_parts(PartStatus::Done(Err(PartFailure::Error(err.clone())))),
+        }
+        parsed
+    });
+
+    // listing all the parts to run on the parsed inputs
+    let mut tasks = vec![];
+    for ((d, _, parts, input, _), parsed) in parse_tasks.iter().zip(parsed.iter()) {
+        match parsed {
+            Ok((parsed, time)) => {
+                results[*d].parse_time = Some(*time);
+                for &part in parts {
+                    tasks.push((*d, part, parsed.as_ref(), *input))
+                }
+            }
+            Err(err) => {
+                for &part in parts {
+                    set_outcome(&mut results[*d], part, Err(PartFailure::Error(err.clone())))
+                }
+            }
+        }
+    }
+    let outcomes = run_pool(jobs, &tasks, |&(d, part, parsed, input)| {
+        if let Some(db) = dashboard {
+            db.set(d, Some(part), PartStatus::Running(Instant::now()))
+        }
+        let outcome =
+            run_part(parsed, part, input).map_err(|err| PartFailure::Error(err.to_string()));
+        if let Some(db) = dashboard {
+            db.set(d, Some(part), PartStatus::Done(outcome.clone()))
+        }
+        outcome
+    });
+
+    for (&(d, part, _, _), outcome) in tasks.iter().zip(outcomes) {
+        set_outcome(&mut results[d], part, outcome)
+    }
+    Ok(results)
+}
+
+/// Run all the requested parts, each in a worker process killed after `timeout`
+fn run_days_isolated(
+    to_run: &[Option<DayInput>],
+    jobs: usize,
+    timeout: Duration,
+    dashboard: Option<&Dashboard>,
+) -> Result<Vec<DayResult>, Box<dyn Error>> {
+    let mut results = vec![DayResult::default(); to_run.len()];
+
+    let mut tasks = vec![];
+    for (d, run) in to_run.iter().enumerate() {
+        if let Some(run) = run {
+            for &part in run.parts.iter() {
+                match &run.loaded {
+                    Ok((input, params)) => tasks.push((d, part, input.as_str(), params)),
+                    Err(err) => {
+                        set_outcome(&mut results[d], part, Err(PartFailure::Error(err.clone())))
+                    }
+                }
+            }
+        }
+    }
+    let outcomes = run_pool(jobs, &tasks, |&(d, part, input, params)| {
+        if let Some(db) = dashboard {
+            db.set(d, Some(part), PartStatus::Running(Instant::now()))
+        }
+        let (parse_time, outcome) = run_worker(d as u8 + 1, part, input, params, timeout)
+            .unwrap_or_else(|err| (None, Err(PartFailure::Error(err.to_string()))));
+        if let Some(db) = dashboard {
+            db.set(d, Some(part), PartStatus::Done(outcome.clone()))
+        }
+        (parse_time, outcome)
+    });
+
+    for (&(d, part, _, _), (parse_time, outcome)) in tasks.iter().zip(outcomes) {
+        // every worker parses the input, the first one to succeed is shown
+        results[d].parse_time = results[d].parse_time.or(parse_time);
+        set_outcome(&mut results[d], part, outcome)
+    }
+    Ok(results)
+}
+
+/// Run a part in a worker process, killing it after `timeout`
+///
+/// Gives the parse time, if the worker got past parsing, and the outcome of the part
+fn run_worker(
+    day: u8,
+    part: usize,
+    input: &str,
+    params: &Params,
+    timeout: Duration,
+) -> Result<(Option<Duration>, PartOutcome), Box<dyn Error>> {
+    let mut command = process::Command::new(env::current_exe()?);
+    command.args(["--worker", "--input", "-"]);
+    command.args(["--day", &day.to_string(), "--part", &part.to_string()]);
+    for (name, value) in params.iter() {
+        command.arg("--param").arg(format!("{name}={value}"));
+    }
+    let mut worker = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|err| format!("Error while starting the worker: {err}"))?;
+
+    let start = Instant::now();
+    let mut stdin = worker.stdin.take().unwrap();
+    let mut stdout = worker.stdout.take().unwrap();
+    // the pipes are served by their own threads, as the worker blocks when one of them is full
+    thread::scope(|scope| {
+        // the worker could die before reading it all, the exit status tells why
+        scope.spawn(move || {
+            let _ = stdin.write_all(input.as_bytes());
+        });
+        let output = scope.spawn(move || {
+            let mut output = String::new();
+            stdout.read_to_string(&mut output).map(|_| output)
+        });
+        let status = loop {
+            if let Some(status) = worker.try_wait()? {
+                break status;
+            }
+            if start.elapsed() >= timeout {
+                // killing it closes the pipes, ending the threads
+                worker.kill()?;
+                worker.wait()?;
+                return Ok((None, Err(PartFailure::Timeout(timeout))));
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+        let output = output.join().expect("The worker output should be read")?;
+        parse_worker_output(&output).ok_or_else(|| format!("Worker failed ({status})").into())
+    })
+}
+
+/// Report the outcome of a part to the parent process
+///
+/// The first line is `ok <parse ns> <part ns> <peak bytes> <allocations> <kind>` or
+/// `error <parse ns or ->`, followed by the result or the error message
+pub(crate) fn worker_output(
+    parse_time: Option<Duration>,
+    outcome: &Result<RunResult, String>,
+) -> String {
+    let parse_ns = parse_time.map_or("-".to_owned(), |t| t.as_nanos().to_string());
+    match outcome {
+        Ok(res) => format!(
+            "ok {parse_ns} {} {} {} {}\n{}",
+            res.time.as_nanos(),
+            res.memory.peak,
+            res.memory.allocations,
+            res.res.kind(),
+            res.res.repr().as_str()
+        ),
+        Err(err) => format!("error {parse_ns}\n{err}"),
+    }
+}
+
+/// Solve the single part of a run in a worker process, giving the report for the parent process
+pub fn solve_in_worker(day: &dyn Day, run: &DayInput) -> Result<String, Box<dyn Error>> {
+    let (input, params) = run.loaded.as_ref().map_err(|err| err.clone())?;
+    let (parse_time, outcome) = match parse_day(day, input, params) {
+        Ok((parsed, time)) => (
+            Some(time),
+            run_part(parsed.as_ref(), run.parts[0], input).map_err(|err| err.to_string()),
+        ),
+        Err(err) => (None, Err(err.to_string())),
+    };
+    Ok(worker_output(parse_time, &outcome))
+}
+
+/// Read the outcome of a part reported by a worker
+pub(crate) fn parse_worker_output(output: &str) -> Option<(Option<Duration>, PartOutcome)> {
+    let (header, body) = output.split_once('\n')?;
+    let mut fields = header.split(' ');
+    let status = fields.next()?;
+    let parse_time = match fields.next()? {
+        "-" => None,
+        ns => Some(Duration::from_nanos(ns.parse().ok()?)),
+    };
+    if status == "error" {
+        return Some((parse_time, Err(PartFailure::Error(body.to_owned()))));
+    }
+    let time = Duration::from_nanos(fields.next()?.parse().ok()?);
+    let memory = MemoryUsage {
+        peak: fields.next()?.parse().ok()?,
+        allocations: fields.next()?.parse().ok()?,
+    };
+    let res = match fields.next()? {
+        "numeric" => PuzzleResult::Numeric(body.parse().ok()?),
+        "textual" => PuzzleResult::Textual(body.to_owned()),
+        "ascii_art" => PuzzleResult::AsciiArt(body.to_owned()),
+        _ => return None,
+    };
+    Some((
+        parse_time,
+        Ok(RunResult {
+            res,
+            time,
+            memory,
+            cached: false,
+            changed: false,
+            check: None,
+        }),
+    ))
+}
+
+/// Record the outcome of a part
+pub(crate) fn set_outcome(result: &mut DayResult, part: usize, outcome: PartOutcome) {
+    if result.parts.len() < part {
+        result.parts.resize(part, None)
+    }
+    result.parts[part - 1] = Some(outcome)
+}
+
+/// Run all the visualizations of a day matching one of the patterns
+pub fn run_visualizations(
+    day: u8,
+    patterns: &[Regex],
+    input: &str,
+    out_dir: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let Some(solution) = crate::day(day) else {
+        return Ok(());
+    };
+    for (name, repr) in solution.visualizations().entries() {
+        let full_name = format!("day{day}/{name}");
+        if patterns.iter().any(|p| p.is_match(&full_name)) {
+            let out_dir = out_dir.join(&full_name);
+            fs::create_dir_all(&out_dir).map_err(|err| {
+                format!(
+                    "Error while creating directory {}: {}",
+                    out_dir.to_string_lossy(),
+                    err
+                )
+            })?;
+            repr(input, &out_dir)
+                .map_err(|err| format!("Error in visualization {full_name}: {err}"))?;
+            eprintln!(
+                "Visualization {full_name} written to {}",
+                out_dir.to_string_lossy()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Normalize an answer, dropping trailing whitespaces and empty lines
+fn normalize_answer(answer: &str) -> String {
+    answer
+        .lines()
+        .map(|l| l.trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end_matches('\n')
+        .to_owned()
+}
+
+/// Check a result against the answer file, if present
+pub fn check_answer(res: &PuzzleResult, answer_path: &Path) -> Result<Check, Box<dyn Error>> {
+    if !answer_path.exists() {
+        return Ok(Check::Unknown);
+    }
+    let expected = normalize_answer(&read_file(answer_path)?);
+    let found = normalize_answer(res.repr().as_str());
+    Ok(if expected == found {
+        Check::Pass
+    } else {
+        Check::Fail { expected }
+    })
+}
+
+/// How the requested parts are solved
+pub struct Solver {
+    /// Number of parts run in parallel
+    pub jobs: usize,
+    /// Run each part in its own process, killing it after this time
+    pub timeout: Option<Duration>,
+    /// Cache of the results, if it is used
+    pub cache: Option<Cache>,
+    /// Solve again the parts with a cached result, updating the cache
+    pub refresh: bool,
+    /// Show a live dashboard of the run, if the output is a terminal
+    pub tui: bool,
+}
+impl Solver {
+    /// Solve the requested parts, using the cache and showing the dashboard if asked
+    ///
+    /// Gives the results and the wall time of the run
+    pub fn solve(
+        &self,
+        to_run: &[Option<DayInput>],
+    ) -> Result<(Vec<DayResult>, Duration), Box<dyn Error>> {
+        // parts with a cached result are not solved again
+        let mut cached = vec![];
+        let mut to_solve = to_run.to_vec();
+        if let (Some(cache), false) = (&self.cache, self.refresh) {
+            for (d, run) in to_solve.iter_mut().enumerate() {
+                if let Some(DayInput {
+                    parts,
+                    loaded: Ok((input, params)),
+                    ..
+                }) = run
+                {
+                    parts.retain(|&part| match cache.get(d + 1, part, input, params) {
+                        Some(res) => {
+                            cached.push((d, part, res));
+                            false
+                        }
+                        None => true,
+                    });
+                    if parts.is_empty() {
+                        *run = None
+                    }
+                }
+            }
+        }
+
+        // the dashboard needs a terminal, otherwise only the results are shown
+        let dashboard = (self.tui && termion::is_tty(&io::stdout())).then(|| {
+            let dashboard = Dashboard::new(to_run);
+            for (d, part, res) in cached.iter() {
+                dashboard.set(*d, Some(*part), PartStatus::Done(Ok(res.clone())))
+            }
+            dashboard
+        });
+
+        let start = Instant::now();
+        let done = AtomicBool::new(false);
+        let mut results = thread::scope(|scope| {
+            let shown = dashboard
+                .as_ref()
+                .map(|dashboard| scope.spawn(|| dashboard.show(&done)));
+            let results = run_days(&to_solve, self.jobs, self.timeout, dashboard.as_ref());
+            done.store(true, Ordering::Relaxed);
+            if let Some(shown) = shown {
+                shown.join().expect("The dashboard should not panic")?;
+            }
+            results
+        })?;
+        let wall_time = start.elapsed();
+
+        if let Some(cache) = &self.cache {
+            for (d, (res, run)) in results.iter().zip(to_solve.iter()).enumerate() {
+                if let Some(DayInput {
+                    loaded: Ok((input, params)),
+                    ..
+                }) = run
+                {
+                    for (i, outcome) in res.parts.iter().enumerate() {
+                        if let Some(Ok(res)) = outcome {
+                            if let Err(err) = cache.put(d + 1, i + 1, input, params, res) {
+                                eprintln!("Day {} part {} not cached: {err}", d + 1, i + 1)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for (d, part, res) in cached {
+            set_outcome(&mut results[d], part, Ok(res))
+        }
+
+        Ok((results, wall_time))
+    }
+}
+
+/// Check the results against the answer files, giving a message for each wrong one
+pub fn check_results(
+    results: &mut [DayResult],
+    to_run: &[Option<DayInput>],
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut failed_checks = vec![];
+    for (d, (res, run)) in results.iter_mut().zip(to_run.iter()).enumerate() {
+        if let Some(DayInput {
+            path: Some(path), ..
+        }) = run
+        {
+            for (i, res) in res.parts.iter_mut().enumerate() {
+                let part = i + 1;
+                if let Some(Ok(res)) = res {
+                    let answer_path = get_related_path(path, &format!("answer{part}"));
+                    let check = check_answer(&res.res, &answer_path)?;
+                    if let Check::Fail { expected } = &check {
+                        failed_checks.push(match res.res.repr() {
+                            ResultRepr::Short(found) => format!(
+                                "Day {} part {part}: expected {expected}, found {found}",
+                                d + 1
+                            ),
+                            ResultRepr::Multiline(found) => format!(
+                                "Day {} part {part}: expected\n{expected}\nfound\n{found}",
+                                d + 1
+                            ),
+                        })
+                    }
+                    res.check = Some(check)
+                }
+            }
+        }
+    }
+    Ok(failed_checks)
+}
+
+/// Files of a day, with their last modification time
+type WatchedFiles = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// Files a day depends on, with their last modification time if they exist
+fn watched_files(run: &DayInput) -> WatchedFiles {
+    let Some(path) = &run.path else {
+        return vec![];
+    };
+    let mut files = vec![path.clone(), get_related_path(path, "params")];
+    for part in run.parts.iter() {
+        files.push(get_related_path(path, &format!("answer{part}")))
+    }
+    files
+        .into_iter()
+        .map(|file| {
+            let modified = fs::metadata(&file).and_then(|m| m.modified()).ok();
+            (file, modified)
+        })
+        .collect()
+}
+
+/// Watch the files of the days to run, to solve again the ones that change
+pub struct Watcher {
+    to_run: Vec<Option<DayInput>>,
+    /// Files of each day to run
+    watched: Vec<Option<WatchedFiles>>,
+    /// Results of the last run of each day
+    results: Vec<DayResult>,
+}
+impl Watcher {
+    pub fn new(to_run: &[Option<DayInput>]) -> Self {
+        Self {
+            to_run: to_run.to_vec(),
+            watched: to_run
+                .iter()
+                .map(|run| run.as_ref().map(watched_files))
+                .collect(),
+            results: vec![DayResult::default(); to_run.len()],
+        }
+    }
+
+    /// Take the results of the days that were run again, giving the results of all the days
+    ///
+    /// The answers that changed from the previous run are marked
+    pub fn update(&mut self, rerun: &[Option<DayInput>], results: Vec<DayResult>) -> &[DayResult] {
+        for (d, result) in results.into_iter().enumerate() {
+            if rerun[d].is_none() {
+                // marks are only for the last run
+                for res in self.results[d].parts.iter_mut().flatten().flatten() {
+                    res.changed = false
+                }
+                continue;
+            }
+            let previous = mem::replace(&mut self.results[d], result);
+            for (i, res) in self.results[d].parts.iter_mut().enumerate() {
+                if let (Some(Ok(res)), Some(Some(Ok(before)))) = (res, previous.parts.get(i)) {
+                    res.changed = res.res.repr().as_str() != before.res.repr().as_str()
+                }
+            }
+        }
+        &self.results
+    }
+
+    /// Wait for the files of some days to change, giving them to be run again
+    pub fn wait_changes(&mut self, overrides: &[(String, String)]) -> Vec<Option<DayInput>> {
+        loop {
+            thread::sleep(WATCH_INTERVAL);
+            let mut changed = vec![None; self.to_run.len()];
+            for (d, run) in self.to_run.iter().enumerate() {
+                let Some(run) = run else {
+                    continue;
+                };
+                let files = watched_files(run);
+                if self.watched[d].as_ref() != Some(&files) {
+                    self.watched[d] = Some(files);
+                    let day = crate::day(d as u8 + 1).unwrap();
+                    changed[d] = Some(DayInput::load(
+                        day,
+                        run.parts.clone(),
+                        run.path.clone(),
+                        overrides,
+                    ))
+                }
+            }
+            if changed.iter().any(|run| run.is_some()) {
+                return changed;
+            }
+        }
+    }
+}
+
+/// Time between two checks of the watched files
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
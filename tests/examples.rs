@@ -0,0 +1,46 @@
+//! Run the examples in the inputs dir, checking the parts with a known answer
+
+use std::path::Path;
+
+use year2022::{
+    inputs::{example_parts, DayInput},
+    runner::{check_results, run_days, PartSpec},
+    DAYS,
+};
+
+#[test]
+fn examples() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+    let mut to_run = vec![None; 25];
+    let mut missing = vec![];
+    for day in DAYS {
+        match example_parts(day, &dir, PartSpec::All) {
+            Ok((parts, input)) => {
+                to_run[day.number() as usize - 1] =
+                    Some(DayInput::load(day, parts, Some(input), &[]))
+            }
+            Err(msg) => missing.push(msg),
+        }
+    }
+    // the days without an example are not a failure, but are worth knowing about
+    for msg in &missing {
+        eprintln!("{msg}")
+    }
+    assert!(
+        missing.len() < DAYS.len(),
+        "No example found in {}",
+        dir.to_string_lossy()
+    );
+
+    let mut results = run_days(&to_run, 1, None, None).unwrap();
+    let mut failed: Vec<String> = vec![];
+    for (d, res) in results.iter().enumerate() {
+        for (i, outcome) in res.parts.iter().enumerate() {
+            if let Some(Err(err)) = outcome {
+                failed.push(format!("Day {} part {} failed: {err}", d + 1, i + 1))
+            }
+        }
+    }
+    failed.extend(check_results(&mut results, &to_run).unwrap());
+    assert!(failed.is_empty(), "{}", failed.join("\n"));
+}